@@ -0,0 +1,29 @@
+//! Data shared by the benchmarks.
+
+use frameshift::provider::celestrak::Entry;
+use frameshift::provider::CelestrakProvider;
+use frameshift::time::{Epoch, UTC};
+
+// about 137 years of daily entries
+const ENTRIES: usize = 50_000;
+
+pub fn provider() -> CelestrakProvider {
+    let entries = (0..ENTRIES)
+        .map(|day| {
+            let t = day as f64;
+            let tai_utc = 10 + (day / 1_500) as i64;
+            Entry::new(
+                Epoch::from_mjd(41_317.0 + t),
+                tai_utc,
+                0.5 * (t / 365.0).sin(),
+            )
+        })
+        .collect();
+    CelestrakProvider::from_entries(entries)
+}
+
+pub fn queries() -> Vec<Epoch<UTC>> {
+    (0..1_000)
+        .map(|i| Epoch::from_mjd(41_317.5 + (i * 49) as f64 + 0.25))
+        .collect()
+}
@@ -0,0 +1,31 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use frameshift::provider::Provider;
+
+mod common;
+
+fn frozen_vs_entries(c: &mut Criterion) {
+    let celestrak = common::provider();
+    let frozen = celestrak.clone().into_frozen();
+    let queries = common::queries();
+
+    let mut group = c.benchmark_group("full_state");
+    group.bench_function("CelestrakProvider", |b| {
+        b.iter(|| {
+            for t in &queries {
+                black_box(celestrak.full_state(black_box(t)));
+            }
+        })
+    });
+    group.bench_function("FrozenProvider", |b| {
+        b.iter(|| {
+            for t in &queries {
+                black_box(frozen.full_state(black_box(t)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, frozen_vs_entries);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+mod common;
+
+fn lookups(c: &mut Criterion) {
+    let provider = common::provider();
+    let queries = common::queries();
+    let queries_tai: Vec<_> = queries
+        .iter()
+        .map(|t| t.to_tai_with(&provider).unwrap())
+        .collect();
+    let queries_ut1: Vec<_> = queries
+        .iter()
+        .map(|t| t.to_ut1_with(&provider).unwrap())
+        .collect();
+
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("get_utc", |b| {
+        b.iter(|| {
+            for t in &queries {
+                black_box(provider.get_utc(black_box(t)));
+            }
+        })
+    });
+    group.bench_function("get_tai", |b| {
+        b.iter(|| {
+            for t in &queries_tai {
+                black_box(provider.get_tai(black_box(t)));
+            }
+        })
+    });
+    group.bench_function("get_ut1", |b| {
+        b.iter(|| {
+            for t in &queries_ut1 {
+                black_box(provider.get_ut1(black_box(t)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookups);
+criterion_main!(benches);
@@ -1,4 +1,10 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod provider;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod time;
+pub mod units;
@@ -0,0 +1,212 @@
+//! Compact binary storage for orientation data.
+//!
+//! Parsing a large CSV file on every start can be slow. This module
+//! saves the [Entry]s in a [CelestrakProvider] in a simple binary
+//! format, which loads much faster.
+//!
+//! The format starts with an 8-byte magic number and a 4-byte format
+//! version, followed by an 8-byte entry count and the entries
+//! themselves. All numbers are little-endian. Files from a different
+//! format version are rejected.
+
+use std::io::{Read, Write};
+
+use super::celestrak::{Entry, Type};
+use super::CelestrakProvider;
+use crate::time::{Epoch, TimeDelta};
+use crate::units::Arcsec;
+
+/// The magic number at the start of every binary file.
+pub const MAGIC: [u8; 8] = *b"FSHIFTEO";
+
+/// The current binary format version.
+pub const VERSION: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("error reading binary data")]
+    Read(#[from] std::io::Error),
+    #[error("the binary data does not start with the expected magic number")]
+    BadMagic,
+    #[error("the binary data has format version {0}, expected {VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("entry {0} of the binary data is corrupt")]
+    Corrupt(usize),
+}
+
+// read a fixed number of little-endian bytes
+fn read_bytes<R, const N: usize>(r: &mut R) -> std::io::Result<[u8; N]>
+where
+    R: Read,
+{
+    let mut buf = [0; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+impl CelestrakProvider {
+    /// Save the entries in this provider in a compact binary format.
+    ///
+    /// See the [module documentation][self] for details. The
+    /// [Interpolation][super::celestrak::Interpolation] setting is
+    /// not saved.
+    pub fn save_binary<W>(&self, mut w: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        w.write_all(&MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&(self.entries().len() as u64).to_le_bytes())?;
+
+        for e in self.entries() {
+            let (secs, nanos) = e.time_utc.to_frameshift().to_raw();
+            w.write_all(&secs.to_le_bytes())?;
+            w.write_all(&nanos.to_le_bytes())?;
+
+            for v in [
+                e.x.0, e.y.0, e.ut1_utc, e.lod, e.dpsi.0, e.deps.0, e.dx.0, e.dy.0,
+            ] {
+                w.write_all(&v.to_le_bytes())?;
+            }
+
+            w.write_all(&e.tai_utc.to_le_bytes())?;
+            w.write_all(&[match e.data_type {
+                Type::Observed => 0,
+                Type::Predicted => 1,
+            }])?;
+        }
+
+        Ok(())
+    }
+
+    /// Load a provider saved by [save_binary][Self::save_binary].
+    pub fn load_binary<R>(file: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let mut r = std::io::BufReader::new(file);
+
+        if read_bytes(&mut r)? != MAGIC {
+            return Err(Error::BadMagic);
+        }
+
+        let version = u32::from_le_bytes(read_bytes(&mut r)?);
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let count = u64::from_le_bytes(read_bytes(&mut r)?);
+        let mut entries = Vec::new();
+        for i in 0..count as usize {
+            let secs = i64::from_le_bytes(read_bytes(&mut r)?);
+            let nanos = u32::from_le_bytes(read_bytes(&mut r)?);
+            let time_utc = TimeDelta::new(secs, nanos)
+                .map(Epoch::from_frameshift)
+                .ok_or(Error::Corrupt(i))?;
+
+            let mut values = [0.0; 8];
+            for v in values.iter_mut() {
+                *v = f64::from_le_bytes(read_bytes(&mut r)?);
+            }
+            let [x, y, ut1_utc, lod, dpsi, deps, dx, dy] = values;
+
+            let tai_utc = i64::from_le_bytes(read_bytes(&mut r)?);
+            let data_type = match read_bytes::<_, 1>(&mut r)? {
+                [0] => Type::Observed,
+                [1] => Type::Predicted,
+                _ => return Err(Error::Corrupt(i)),
+            };
+
+            entries.push(Entry {
+                time_utc,
+                x: Arcsec(x),
+                y: Arcsec(y),
+                ut1_utc,
+                lod,
+                dpsi: Arcsec(dpsi),
+                deps: Arcsec(deps),
+                dx: Arcsec(dx),
+                dy: Arcsec(dy),
+                tai_utc,
+                data_type,
+            });
+        }
+
+        Ok(Self::from_entries(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // header, then each entry
+    const HEADER_LEN: usize = 20;
+    const ENTRY_LEN: usize = 85;
+
+    fn provider() -> CelestrakProvider {
+        CelestrakProvider::builder()
+            .push(Entry {
+                x: Arcsec(0.1),
+                lod: f64::NAN,
+                ..Entry::new(Epoch::from_mjd(57000.25), 35, -0.4)
+            })
+            .push(Entry {
+                data_type: Type::Predicted,
+                ..Entry::new(Epoch::from_mjd(57001.0), 36, 0.55)
+            })
+            .build()
+    }
+
+    fn saved() -> Vec<u8> {
+        let mut data = Vec::new();
+        provider().save_binary(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = saved();
+        assert_eq!(data.len(), HEADER_LEN + 2 * ENTRY_LEN);
+
+        let back = CelestrakProvider::load_binary(&data[..]).unwrap();
+        for (a, b) in provider().iter().zip(back.iter()) {
+            assert_eq!(a.time_utc, b.time_utc);
+            assert_eq!((a.x, a.ut1_utc, a.tai_utc), (b.x, b.ut1_utc, b.tai_utc));
+            assert_eq!(a.lod.is_nan(), b.lod.is_nan());
+            assert_eq!(a.data_type, b.data_type);
+        }
+        assert_eq!(back.entries().len(), 2);
+    }
+
+    #[test]
+    fn rejects_bad_data() {
+        let mut data = saved();
+        data[0] = b'X';
+        assert!(matches!(
+            CelestrakProvider::load_binary(&data[..]),
+            Err(Error::BadMagic)
+        ));
+
+        let mut data = saved();
+        data[8..12].copy_from_slice(&2u32.to_le_bytes());
+        assert!(matches!(
+            CelestrakProvider::load_binary(&data[..]),
+            Err(Error::UnsupportedVersion(2))
+        ));
+
+        // the data type byte ends each entry
+        let mut data = saved();
+        data[HEADER_LEN + 2 * ENTRY_LEN - 1] = 7;
+        assert!(matches!(
+            CelestrakProvider::load_binary(&data[..]),
+            Err(Error::Corrupt(1))
+        ));
+
+        let data = saved();
+        assert!(matches!(
+            CelestrakProvider::load_binary(&data[..data.len() - 1]),
+            Err(Error::Read(ref e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+    }
+}
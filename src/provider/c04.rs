@@ -0,0 +1,173 @@
+//! IERS C04 orientation data.
+//!
+//! This module reads the fixed-column [EOP 14 C04 series][c04]
+//! published by the IERS, in its IAU 2000 form
+//! (`eopc04_IAU2000.62-now`). This is a homogeneous daily series
+//! going back to 1962, and is the preferred source for analyses
+//! spanning many decades.
+//!
+//! [c04]: https://hpiers.obspm.fr/iers/eop/eopc04/
+//!
+//! The C04 files do not contain TAI - UTC, so a second [Provider] is
+//! needed to supply leap second info. The C04 files also do not
+//! contain the nutation corrections `dpsi` and `deps`, which are
+//! filled with NaN.
+//!
+//! The resulting [Entry]s can be loaded into a [CelestrakProvider].
+
+use std::io::BufRead;
+
+use super::celestrak::{Entry, Type};
+use super::{CelestrakProvider, Provider};
+use crate::time::{Epoch, TimeDelta};
+use crate::units::Arcsec;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("error reading C04 data")]
+    Read(#[from] std::io::Error),
+    #[error("line {0} of the C04 file is missing column {1}")]
+    MissingField(usize, &'static str),
+    #[error("column {1} of line {0} of the C04 file failed to parse")]
+    BadParse(usize, &'static str),
+    #[error("no TAI - UTC is available for line {0} of the C04 file")]
+    MissingTaiUtc(usize),
+}
+
+macro_rules! get_column {
+    ($linei:expr, $row:expr, $i:expr, $name:expr) => {
+        $row.get($i)
+            .ok_or_else(|| Error::MissingField($linei, $name))
+            .and_then(|s| s.parse().map_err(|_| Error::BadParse($linei, $name)))
+    };
+}
+
+/// Read all [Entry]s from a C04 file.
+///
+/// TAI - UTC for each entry is taken from `leap_seconds`. Line
+/// numbers in errors start at 1.
+pub fn read_entries<R, P>(file: R, leap_seconds: &P) -> Result<Vec<Entry>, Error>
+where
+    R: std::io::Read,
+    P: Provider,
+{
+    let mut entries = Vec::new();
+    let mut in_header = true;
+    for (linei, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let linei = linei + 1;
+        let row: Vec<&str> = line.split_whitespace().collect();
+
+        if row.is_empty() {
+            continue;
+        }
+
+        // the header is free-form, data starts at the first line
+        // that begins with a year
+        if in_header {
+            if row.len() < 10 || row[0].parse::<i32>().is_err() {
+                continue;
+            }
+            in_header = false;
+        }
+
+        let time_utc = Epoch::from_modified_julian_day(TimeDelta::from_days(get_column!(
+            linei, row, 3, "MJD"
+        )?));
+
+        // pre-1972 TAI - UTC is not integral, but this is the best we can do
+        let tai_utc = leap_seconds
+            .tai_utc_for_utc(&time_utc)
+            .ok_or(Error::MissingTaiUtc(linei))?
+            .to_raw()
+            .0;
+
+        entries.push(Entry {
+            time_utc,
+            x: Arcsec(get_column!(linei, row, 4, "x")?),
+            y: Arcsec(get_column!(linei, row, 5, "y")?),
+            ut1_utc: get_column!(linei, row, 6, "UT1-UTC")?,
+            lod: get_column!(linei, row, 7, "LOD")?,
+            dpsi: Arcsec::NAN,
+            deps: Arcsec::NAN,
+            dx: Arcsec(get_column!(linei, row, 8, "dX")?),
+            dy: Arcsec(get_column!(linei, row, 9, "dY")?),
+            tai_utc,
+            data_type: Type::Observed,
+        });
+    }
+
+    Ok(entries)
+}
+
+impl CelestrakProvider {
+    /// Load a provider from a C04 file, taking TAI - UTC from
+    /// `leap_seconds`.
+    ///
+    /// See [read_entries].
+    pub fn from_c04<R, P>(file: R, leap_seconds: &P) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+        P: Provider,
+    {
+        Ok(Self::from_entries(read_entries(file, leap_seconds)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const C04: &str = "\
+                           EARTH ORIENTATION PARAMETER (EOP) PRODUCT CENTER
+ FORMAT(3(I4),I7,2(F11.6),2(F12.7),2(F11.6),2(F11.6),2(F11.7),2F12.6)
+  YR  MM  DD  MJD      x(\")      y(\")    UT1-UTC(s)    LOD(s)     dX(\")     dY(\")
+
+2017   1   1  57754   0.035296   0.276830   0.5919117   0.0009420   0.000104  -0.000056
+2017   1   2  57755   0.033596   0.276620   0.5910058   0.0008563   0.000099  -0.000052
+";
+
+    fn leap_seconds() -> CelestrakProvider {
+        CelestrakProvider::from_rows(&[
+            (57_700.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_800.0, 37, 0.0),
+        ])
+    }
+
+    #[test]
+    fn read_c04() {
+        let entries = read_entries(C04.as_bytes(), &leap_seconds()).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let entry = &entries[0];
+        assert_eq!(entry.time_utc, Epoch::from_mjd(57_754.0));
+        assert_eq!(entry.x, Arcsec(0.035296));
+        assert_eq!(entry.y, Arcsec(0.276830));
+        assert_eq!(entry.ut1_utc, 0.5919117);
+        assert_eq!(entry.lod, 0.0009420);
+        assert_eq!(entry.dx, Arcsec(0.000104));
+        assert_eq!(entry.dy, Arcsec(-0.000056));
+        assert!(entry.dpsi.is_nan() && entry.deps.is_nan());
+        assert_eq!(entry.tai_utc, 37);
+        assert!(entry.is_observed());
+
+        let provider = CelestrakProvider::from_c04(C04.as_bytes(), &leap_seconds()).unwrap();
+        assert_eq!(provider.entries().len(), 2);
+    }
+
+    #[test]
+    fn read_c04_errors() {
+        let empty = crate::provider::EmptyProvider;
+        assert!(matches!(
+            read_entries(C04.as_bytes(), &empty),
+            Err(Error::MissingTaiUtc(5))
+        ));
+
+        let bad = C04.replace("0.5910058", "0.59x0058");
+        assert!(matches!(
+            read_entries(bad.as_bytes(), &leap_seconds()),
+            Err(Error::BadParse(6, "UT1-UTC"))
+        ));
+    }
+}
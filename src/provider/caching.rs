@@ -0,0 +1,226 @@
+//! Provider wrapper that remembers previous lookups.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::{EopState, LeapSecondsIter, Provider};
+use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+/// A [Provider] that forwards to another provider, and caches the
+/// results of time scale lookups.
+///
+/// Conversions between time scales often query the provider several
+/// times for the same epoch. This wrapper remembers every answer,
+/// including [None], so each epoch only reaches the inner provider
+/// once.
+///
+/// TAI - UTC and UT1 - UTC are cached by the exact epoch of the
+/// query. Other parameters are not cached.
+///
+/// The cache grows without bound, and uses interior mutability, so
+/// this provider is not [Sync]. Use one per thread.
+#[derive(Debug)]
+pub struct CachingProvider<P> {
+    inner: P,
+    tai_utc_for_utc: Cache<(i64, u32), Option<TimeDelta<TAI>>>,
+    tai_utc_for_tai: Cache<(i64, u32), Option<TimeDelta<TAI>>>,
+    ut1_utc_for_utc: Cache<(i64, u32), Option<TimeDelta<UT1>>>,
+    ut1_utc_for_ut1: Cache<(i64, u32), Option<TimeDelta<UT1>>>,
+}
+
+type Cache<K, V> = RefCell<HashMap<K, V>>;
+
+// look up key in cache, or compute and store it
+fn cached<K, V, F>(cache: &Cache<K, V>, key: K, f: F) -> V
+where
+    K: std::hash::Hash + Eq,
+    V: Copy,
+    F: FnOnce() -> V,
+{
+    if let Some(value) = cache.borrow().get(&key) {
+        return *value;
+    }
+
+    // don't hold the borrow while calling into the inner provider
+    let value = f();
+    cache.borrow_mut().insert(key, value);
+    value
+}
+
+impl<P> CachingProvider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            tai_utc_for_utc: RefCell::default(),
+            tai_utc_for_tai: RefCell::default(),
+            ut1_utc_for_utc: RefCell::default(),
+            ut1_utc_for_ut1: RefCell::default(),
+        }
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Unwrap this provider, discarding the cache.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Forget all cached lookups.
+    pub fn clear(&self) {
+        self.tai_utc_for_utc.borrow_mut().clear();
+        self.tai_utc_for_tai.borrow_mut().clear();
+        self.ut1_utc_for_utc.borrow_mut().clear();
+        self.ut1_utc_for_ut1.borrow_mut().clear();
+    }
+}
+
+impl<P> Provider for CachingProvider<P>
+where
+    P: Provider,
+{
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        cached(
+            &self.tai_utc_for_utc,
+            epoch.to_frameshift().to_raw(),
+            || self.inner.tai_utc_for_utc(epoch),
+        )
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        cached(
+            &self.tai_utc_for_tai,
+            epoch.to_frameshift().to_raw(),
+            || self.inner.tai_utc_for_tai(epoch),
+        )
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        cached(
+            &self.ut1_utc_for_utc,
+            epoch.to_frameshift().to_raw(),
+            || self.inner.ut1_utc_for_utc(epoch),
+        )
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        cached(
+            &self.ut1_utc_for_ut1,
+            epoch.to_frameshift().to_raw(),
+            || self.inner.ut1_utc_for_ut1(epoch),
+        )
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner.polar_motion_for_utc(epoch)
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        self.inner.lod_for_utc(epoch)
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner.nutation_corrections_for_utc(epoch)
+    }
+
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        self.inner.gps_utc_for_utc(epoch)
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.inner.leap_seconds()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        self.inner.leap_seconds_iter()
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        self.inner.full_state(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::provider::{CelestrakProvider, SmearingProvider};
+
+    // counts every call that reaches it
+    #[derive(Default)]
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl Provider for Counting {
+        fn tai_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+            self.calls.set(self.calls.get() + 1);
+            TimeDelta::new(37, 0)
+        }
+
+        fn tai_utc_for_tai(&self, _epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+            self.calls.set(self.calls.get() + 1);
+            TimeDelta::new(37, 0)
+        }
+
+        fn ut1_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+            self.calls.set(self.calls.get() + 1);
+            None
+        }
+
+        fn ut1_utc_for_ut1(&self, _epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+            self.calls.set(self.calls.get() + 1);
+            None
+        }
+    }
+
+    #[test]
+    fn queries_inner_once() {
+        let provider = CachingProvider::new(Counting::default());
+        let epoch = Epoch::<UTC>::from_mjd(60_000.25);
+
+        for _ in 0..10 {
+            assert_eq!(provider.tai_utc_for_utc(&epoch), TimeDelta::new(37, 0));
+            assert_eq!(provider.ut1_utc_for_utc(&epoch), None);
+        }
+        assert_eq!(provider.inner().calls.get(), 2);
+
+        provider.tai_utc_for_utc(&(epoch + TimeDelta::new(0, 1).unwrap()));
+        assert_eq!(provider.inner().calls.get(), 3);
+
+        provider.clear();
+        provider.tai_utc_for_utc(&epoch);
+        assert_eq!(provider.inner().calls.get(), 4);
+    }
+
+    #[test]
+    fn smeared_offsets_are_exact() {
+        let rows = [
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_755.0, 37, 0.0),
+        ];
+        let smearing = SmearingProvider::new(CelestrakProvider::from_rows(&rows));
+        let provider = CachingProvider::new(smearing.clone());
+
+        // inside the smear window, a fraction of a second apart
+        let start = Epoch::<UTC>::from_mjd(57_753.75);
+        for nanos in [0, 1_000, 500_000_000, 999_999_999] {
+            let epoch = start + TimeDelta::new(0, nanos).unwrap();
+            assert_eq!(
+                provider.tai_utc_for_utc(&epoch),
+                smearing.tai_utc_for_utc(&epoch)
+            );
+
+            let tai = epoch.to_tai_with(&smearing).unwrap();
+            assert_eq!(
+                provider.tai_utc_for_tai(&tai),
+                smearing.tai_utc_for_tai(&tai)
+            );
+        }
+    }
+}
@@ -11,14 +11,105 @@
 //!
 //! [long]: https://celestrak.org/SpaceData/EOP-All.csv
 //! [short]: https://celestrak.org/SpaceData/EOP-Last5Years.csv
+//!
+//! With the `download` feature enabled, these can be fetched directly
+//! with `CelestrakProvider::from_celestrak_all` and
+//! `CelestrakProvider::from_celestrak_last5years`.
 
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 
+use super::{EopState, FrozenProvider, LeapSecondsIter};
 use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+/// URL of the Celestrak file containing all data so far.
+pub const CELESTRAK_ALL_URL: &str = "https://celestrak.org/SpaceData/EOP-All.csv";
+
+/// URL of the Celestrak file containing only the last 5 years.
+pub const CELESTRAK_LAST5YEARS_URL: &str = "https://celestrak.org/SpaceData/EOP-Last5Years.csv";
 
 #[derive(Debug, Clone)]
 pub struct CelestrakProvider {
     entries: Vec<Entry>,
+    // the time of each entry on each axis, for binary search
+    times_utc: Vec<Epoch<UTC>>,
+    times_tai: Vec<Epoch<TAI>>,
+    times_ut1: Vec<Epoch<UT1>>,
+    interpolation: Interpolation,
+    extrapolation: ExtrapolationPolicy,
+    type_boundary: TypeBoundary,
+}
+
+/// How [CelestrakProvider] computes values between entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Interpolation {
+    /// Use the closest entry, without any smoothing.
+    ///
+    /// As with [CubicSpline][Self::CubicSpline], UT1 - UTC is taken
+    /// as UT1 - TAI, so that the closest entry on the far side of a
+    /// leap second does not shift it by a whole second.
+    Nearest,
+
+    /// Interpolate linearly between the two surrounding entries.
+    #[default]
+    Linear,
+
+    /// Interpolate with a Catmull-Rom spline through the four
+    /// surrounding entries.
+    ///
+    /// UT1 - UTC is interpolated as UT1 - TAI, to avoid ringing
+    /// around leap seconds.
+    CubicSpline,
+}
+
+/// How [CelestrakProvider] answers queries outside of its data.
+///
+/// TAI - UTC is never extrapolated. Outside of the data it always
+/// holds the value from the nearest entry. Extrapolated values are
+/// always marked [Type::Predicted].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ExtrapolationPolicy {
+    /// Return [None] outside of the data.
+    #[default]
+    None,
+
+    /// Use the first or last entry.
+    Clamp,
+
+    /// Continue the line through the first two, or last two, entries.
+    Linear,
+}
+
+/// How [CelestrakProvider] interpolates between an observed and a
+/// predicted entry.
+///
+/// There can be a large jump between the last observed entry and the
+/// first predicted one, so blending them can give values that are
+/// neither.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TypeBoundary {
+    /// Interpolate across the boundary as usual, and mark the result
+    /// [Type::Predicted].
+    #[default]
+    Blend,
+
+    /// Do not interpolate across the boundary. Between an observed
+    /// and a predicted entry, use the observed entry unchanged. Cubic
+    /// splines also do not reach across the boundary.
+    Hold,
+}
+
+/// Which entry [CelestrakProvider::merge] keeps when there is more
+/// than one entry at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MergeRule {
+    /// Keep the [Type::Observed] entry over a [Type::Predicted] one.
+    /// If both have the same type, keep the first.
+    #[default]
+    PreferObserved,
+
+    /// Always keep the first entry.
+    PreferFirst,
 }
 
 #[derive(Debug, Clone)]
@@ -27,10 +118,10 @@ pub struct Entry {
     pub time_utc: Epoch<UTC>,
 
     /// Arc-seconds.
-    pub x: f64,
+    pub x: Arcsec,
 
     /// Arc-seconds.
-    pub y: f64,
+    pub y: Arcsec,
 
     /// UT1 - UTC, seconds.
     pub ut1_utc: f64,
@@ -39,16 +130,16 @@ pub struct Entry {
     pub lod: f64,
 
     /// Arc-seconds.
-    pub dpsi: f64,
+    pub dpsi: Arcsec,
 
     /// Arc-seconds.
-    pub deps: f64,
+    pub deps: Arcsec,
 
     /// Arc-seconds.
-    pub dx: f64,
+    pub dx: Arcsec,
 
     /// Arc-seconds.
-    pub dy: f64,
+    pub dy: Arcsec,
 
     /// TAI - UTC, seconds.
     pub tai_utc: i64,
@@ -57,7 +148,29 @@ pub struct Entry {
     pub data_type: Type,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// An [Entry] looked up by
+/// [get_utc_detailed][CelestrakProvider::get_utc_detailed], with
+/// details about how it was computed.
+#[derive(Debug, Clone)]
+pub struct Interpolated {
+    /// The interpolated entry, as returned by
+    /// [get_utc][CelestrakProvider::get_utc].
+    pub entry: Entry,
+
+    /// How far the query is from the first bracketing entry to the
+    /// second, from 0.0 to 1.0.
+    ///
+    /// This is outside that range if the entry was extrapolated.
+    pub fraction: f64,
+
+    /// The times of the two entries used.
+    ///
+    /// When extrapolating, these are the two entries nearest the edge
+    /// of the data, which may be the same entry.
+    pub bracketing_times: (Epoch<UTC>, Epoch<UTC>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Type {
     Observed,
     Predicted,
@@ -67,6 +180,8 @@ pub enum Type {
 pub enum Error {
     #[error("error reading CSV data")]
     Read(#[from] std::io::Error),
+    #[error("error writing CSV data")]
+    Write(#[source] std::io::Error),
     #[error("the CSV file does not have a header")]
     MissingHeader,
     #[error("the CSV file does not contain a {0} column")]
@@ -75,6 +190,15 @@ pub enum Error {
     MissingField(usize, &'static str),
     #[error("column {1} of row {0} of the CSV file failed to parse")]
     BadParse(usize, &'static str),
+    #[error("the CSV file exceeds the limit on {0}")]
+    LimitExceeded(&'static str),
+    #[error("entry {0} has the same time as the entry before it")]
+    DuplicateEpoch(usize),
+    #[error("entry {0} is earlier than the entry before it")]
+    NonMonotonic(usize),
+    #[cfg(feature = "download")]
+    #[error("error downloading CSV data")]
+    Http(#[from] reqwest::Error),
 }
 
 macro_rules! find_column {
@@ -98,12 +222,112 @@ macro_rules! get_column {
     };
 }
 
+// parse a float, treating blank fields as NaN
+fn parse_or_nan(s: &str) -> Result<f64, std::num::ParseFloatError> {
+    if s.trim().is_empty() {
+        Ok(f64::NAN)
+    } else {
+        s.parse()
+    }
+}
+
+// interpolate, but use whichever end is known if the other is NaN
+pub(super) fn lerp_known(v0: f64, v1: f64, g1: f64) -> f64 {
+    if v0.is_nan() {
+        v1
+    } else if v1.is_nan() {
+        v0
+    } else {
+        (1.0 - g1) * v0 + g1 * v1
+    }
+}
+
+// read a single line, stopping early if it is too long
+fn read_line_limited<B>(reader: &mut B, max_len: usize) -> Result<Option<String>, Error>
+where
+    B: BufRead,
+{
+    // leave room for \r\n
+    let mut buf = Vec::new();
+    let limit = (max_len as u64).saturating_add(2);
+    reader.by_ref().take(limit).read_until(b'\n', &mut buf)?;
+
+    if buf.is_empty() {
+        return Ok(None);
+    }
+
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+    }
+
+    if buf.len() > max_len {
+        return Err(Error::LimitExceeded("line length"));
+    }
+
+    String::from_utf8(buf)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e).into())
+}
+
 impl CelestrakProvider {
+    /// Load a provider from a Celestrak CSV file.
+    ///
+    /// Blank LOD, DPSI, DEPS, DX and DY fields, common in predicted
+    /// rows, are read as NaN.
     pub fn from_csv<R>(file: R) -> Result<Self, Error>
     where
         R: std::io::Read,
     {
-        let mut lines = std::io::BufReader::new(file).lines();
+        Self::from_csv_limited(file, usize::MAX, usize::MAX)
+    }
+
+    /// Download a CSV file from `url` and load it.
+    ///
+    /// This blocks until the whole file has been read. Requires the
+    /// `download` feature.
+    #[cfg(feature = "download")]
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let response = reqwest::blocking::get(url)?.error_for_status()?;
+        Self::from_csv(response)
+    }
+
+    /// Download and load [all data so far][CELESTRAK_ALL_URL].
+    ///
+    /// Requires the `download` feature.
+    #[cfg(feature = "download")]
+    pub fn from_celestrak_all() -> Result<Self, Error> {
+        Self::from_url(CELESTRAK_ALL_URL)
+    }
+
+    /// Download and load [the last 5 years of
+    /// data][CELESTRAK_LAST5YEARS_URL].
+    ///
+    /// Requires the `download` feature.
+    #[cfg(feature = "download")]
+    pub fn from_celestrak_last5years() -> Result<Self, Error> {
+        Self::from_url(CELESTRAK_LAST5YEARS_URL)
+    }
+
+    /// Like [from_csv][Self::from_csv], but with bounded memory use.
+    ///
+    /// Fails with [Error::LimitExceeded] if the file has more than
+    /// `max_entries` rows, or any line longer than `max_line_len`
+    /// bytes (not counting the newline). This is appropriate for
+    /// untrusted input.
+    pub fn from_csv_limited<R>(
+        file: R,
+        max_entries: usize,
+        max_line_len: usize,
+    ) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+    {
+        let mut reader = std::io::BufReader::new(file);
+        let mut lines =
+            std::iter::from_fn(|| read_line_limited(&mut reader, max_line_len).transpose());
 
         let header_line = lines.next().ok_or_else(|| Error::MissingHeader)??;
         let header = header_line.split(',');
@@ -124,20 +348,24 @@ impl CelestrakProvider {
         for line in lines {
             let line = line?;
             let rowi = entries.len();
+            if rowi >= max_entries {
+                return Err(Error::LimitExceeded("entries"));
+            }
+
             let row: Vec<&str> = line.split(',').collect();
 
             let entry = Entry {
                 time_utc: Epoch::from_modified_julian_day(TimeDelta::from_days(get_column!(
                     rowi, row, i_time_utc, "MJD"
                 )?)),
-                x: get_column!(rowi, row, i_x, "X")?,
-                y: get_column!(rowi, row, i_y, "Y")?,
+                x: Arcsec(get_column!(rowi, row, i_x, "X")?),
+                y: Arcsec(get_column!(rowi, row, i_y, "Y")?),
                 ut1_utc: get_column!(rowi, row, i_ut1_utc, "UT1-UTC")?,
-                lod: get_column!(rowi, row, i_lod, "LOD")?,
-                dpsi: get_column!(rowi, row, i_dpsi, "DPSI")?,
-                deps: get_column!(rowi, row, i_deps, "DEPS")?,
-                dx: get_column!(rowi, row, i_dx, "DX")?,
-                dy: get_column!(rowi, row, i_dy, "DY")?,
+                lod: get_column!(rowi, row, i_lod, "LOD", parse_or_nan)?,
+                dpsi: Arcsec(get_column!(rowi, row, i_dpsi, "DPSI", parse_or_nan)?),
+                deps: Arcsec(get_column!(rowi, row, i_deps, "DEPS", parse_or_nan)?),
+                dx: Arcsec(get_column!(rowi, row, i_dx, "DX", parse_or_nan)?),
+                dy: Arcsec(get_column!(rowi, row, i_dy, "DY", parse_or_nan)?),
                 tai_utc: get_column!(rowi, row, i_tai_utc, "DAT")?,
 
                 data_type: get_column!(rowi, row, i_data_type, "DATA_TYPE")?,
@@ -149,45 +377,509 @@ impl CelestrakProvider {
         Ok(Self::from_entries(entries))
     }
 
+    /// Write this provider as a CSV file, readable by
+    /// [from_csv][Self::from_csv].
+    ///
+    /// Only the columns used by this crate are written. Unknown
+    /// values are left blank where the format allows it. Errors from
+    /// `w` are returned as [Error::Write].
+    pub fn to_csv<W>(&self, mut w: W) -> Result<(), Error>
+    where
+        W: std::io::Write,
+    {
+        // blank for NaN, which from_csv reads back as NaN
+        let blankable = |v: f64| {
+            if v.is_nan() {
+                String::new()
+            } else {
+                v.to_string()
+            }
+        };
+
+        writeln!(w, "MJD,X,Y,UT1-UTC,LOD,DPSI,DEPS,DX,DY,DAT,DATA_TYPE").map_err(Error::Write)?;
+        for e in &self.entries {
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                e.time_utc.to_modified_julian_day().to_days(),
+                e.x.0,
+                e.y.0,
+                e.ut1_utc,
+                blankable(e.lod),
+                blankable(e.dpsi.0),
+                blankable(e.deps.0),
+                blankable(e.dx.0),
+                blankable(e.dy.0),
+                e.tai_utc,
+                e.data_type,
+            )
+            .map_err(Error::Write)?;
+        }
+
+        Ok(())
+    }
+
+    /// Build a provider from `(mjd, tai_utc, ut1_utc)` rows.
+    ///
+    /// This is a shorthand for tests and examples. All other fields
+    /// are zero, and every entry is [Type::Observed].
+    pub fn from_rows(rows: &[(f64, i64, f64)]) -> Self {
+        Self::from_entries(
+            rows.iter()
+                .map(|&(mjd, tai_utc, ut1_utc)| {
+                    let time_utc = Epoch::from_modified_julian_day(TimeDelta::from_days(mjd));
+                    Entry::new(time_utc, tai_utc, ut1_utc)
+                })
+                .collect(),
+        )
+    }
+
+    /// Start building a provider one [Entry] at a time.
+    ///
+    /// Unlike [from_rows][Self::from_rows], this can also add complete
+    /// entries, with polar motion or predicted data, using
+    /// [Builder::push].
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Build a provider from [Entry]s in any order.
+    ///
+    /// The entries are sorted by time. Entries at the same time are
+    /// kept, which is lenient but may give surprising lookups. See
+    /// [from_entries_checked][Self::from_entries_checked].
     pub fn from_entries(mut entries: Vec<Entry>) -> Self {
         entries.sort_by_key(|e| e.time_utc);
-        Self { entries }
+        Self {
+            times_utc: entries.iter().map(|e| e.time_utc).collect(),
+            times_tai: entries.iter().map(|e| e.time_tai()).collect(),
+            times_ut1: entries.iter().map(|e| e.time_ut1()).collect(),
+            entries,
+            interpolation: Interpolation::default(),
+            extrapolation: ExtrapolationPolicy::default(),
+            type_boundary: TypeBoundary::default(),
+        }
     }
 
-    pub fn get_utc(&self, t: &Epoch<UTC>) -> Option<Entry> {
-        let idx = self.entries.iter().position(|e| e.time_utc > *t)?;
+    /// Use a different [ExtrapolationPolicy] for lookups outside of
+    /// the data.
+    ///
+    /// The default is [ExtrapolationPolicy::None]. A provider made by
+    /// [into_frozen][Self::into_frozen] never extrapolates, and
+    /// returns [None] outside of the data whatever this is set to.
+    pub fn with_extrapolation(self, extrapolation: ExtrapolationPolicy) -> Self {
+        Self {
+            extrapolation,
+            ..self
+        }
+    }
 
-        if idx == 0 {
-            return None;
+    /// Use a different [TypeBoundary] when interpolating between
+    /// observed and predicted entries.
+    ///
+    /// The default is [TypeBoundary::Blend]. A provider made by
+    /// [into_frozen][Self::into_frozen] keeps only the values, not the
+    /// data types, so it always blends.
+    pub fn with_type_boundary(self, type_boundary: TypeBoundary) -> Self {
+        Self {
+            type_boundary,
+            ..self
+        }
+    }
+
+    /// Like [from_entries][Self::from_entries], but the entries must
+    /// already be sorted by time, with no two at the same time.
+    ///
+    /// Fails with [Error::DuplicateEpoch] or [Error::NonMonotonic],
+    /// naming the index of the first offending entry.
+    pub fn from_entries_checked(entries: Vec<Entry>) -> Result<Self, Error> {
+        for (i, pair) in entries.windows(2).enumerate() {
+            match pair[0].time_utc.cmp(&pair[1].time_utc) {
+                std::cmp::Ordering::Less => {}
+                std::cmp::Ordering::Equal => return Err(Error::DuplicateEpoch(i + 1)),
+                std::cmp::Ordering::Greater => return Err(Error::NonMonotonic(i + 1)),
+            }
         }
 
-        Some(self.entries[idx - 1].lerp(&self.entries[idx], *t - self.entries[idx].time_utc))
+        Ok(Self::from_entries(entries))
     }
 
-    pub fn get_tai(&self, t: &Epoch<TAI>) -> Option<Entry> {
-        let idx = self.entries.iter().position(|e| e.time_tai() > *t)?;
+    /// Combine the entries of two providers, such as a historical
+    /// archive and a fresh file of predictions.
+    ///
+    /// Where there is more than one entry at the same time, `rule`
+    /// picks which one to keep. This includes duplicates within `a`
+    /// or within `b`, where the earlier entry counts as the first.
+    /// The result uses the interpolation and extrapolation settings
+    /// of `a`.
+    pub fn merge(a: Self, b: Self, rule: MergeRule) -> Self {
+        // sorted by time, with entries from a first at each time
+        let mut entries = Vec::with_capacity(a.entries.len() + b.entries.len());
+        let mut others = b.entries.into_iter().peekable();
+        for entry in a.entries {
+            while let Some(other) = others.next_if(|e| e.time_utc < entry.time_utc) {
+                entries.push(other);
+            }
+            entries.push(entry);
+        }
+        entries.extend(others);
+
+        entries.dedup_by(|later, kept| {
+            if later.time_utc != kept.time_utc {
+                return false;
+            }
+            if rule == MergeRule::PreferObserved && later.is_observed() && !kept.is_observed() {
+                std::mem::swap(later, kept);
+            }
+            true
+        });
+
+        Self {
+            interpolation: a.interpolation,
+            extrapolation: a.extrapolation,
+            type_boundary: a.type_boundary,
+            ..Self::from_entries(entries)
+        }
+    }
 
-        if idx == 0 {
+    /// Use a different [Interpolation] for lookups.
+    ///
+    /// The default is [Interpolation::Linear]. This does not carry
+    /// over to [into_frozen][Self::into_frozen], which is always
+    /// linear.
+    pub fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    /// All entries in this provider, sorted by time.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Iterate over all entries in this provider, sorted by time.
+    pub fn iter(&self) -> std::slice::Iter<'_, Entry> {
+        self.entries.iter()
+    }
+
+    /// The entries measured between `start` and `end`, inclusive.
+    pub fn entries_between(&self, start: &Epoch<UTC>, end: &Epoch<UTC>) -> &[Entry] {
+        let lo = self.entries.partition_point(|e| e.time_utc < *start);
+        let hi = self.entries.partition_point(|e| e.time_utc <= *end);
+        &self.entries[lo..hi.max(lo)]
+    }
+
+    /// The times of the first and last entries, or [None] if this
+    /// provider is empty.
+    ///
+    /// Lookups are only possible between these times.
+    pub fn coverage(&self) -> Option<(Epoch<UTC>, Epoch<UTC>)> {
+        Some((
+            self.entries.first()?.time_utc,
+            self.entries.last()?.time_utc,
+        ))
+    }
+
+    /// A new provider containing only the [Type::Observed] entries
+    /// in this one.
+    pub fn observed_only(&self) -> Self {
+        Self {
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            type_boundary: self.type_boundary,
+            ..Self::from_entries(
+                self.entries
+                    .iter()
+                    .filter(|e| e.is_observed())
+                    .cloned()
+                    .collect(),
+            )
+        }
+    }
+
+    /// Whether a lookup at the given UTC [Epoch] would use observed
+    /// or predicted data.
+    ///
+    /// This is [Type::Predicted] if either sample used is predicted.
+    /// Returns [None] if data for this epoch is not available.
+    pub fn data_type_for_utc(&self, t: &Epoch<UTC>) -> Option<Type> {
+        Some(self.get_utc(t)?.data_type)
+    }
+
+    /// Freeze this provider into a [FrozenProvider], optimized for
+    /// lookups.
+    pub fn into_frozen(self) -> FrozenProvider {
+        FrozenProvider::from_sorted_entries(&self.entries)
+    }
+
+    // index of the first entry after t, if there is an entry before t
+    // too. times_tai and times_ut1 are just as sorted as times_utc,
+    // since their offsets change much slower than the sample rate.
+    fn locate<T>(t: &T, keys: &[T]) -> Option<usize>
+    where
+        T: Ord,
+    {
+        let idx = keys.partition_point(|k| k <= t);
+
+        if idx == 0 || idx == keys.len() {
             return None;
         }
 
-        // FIXME this lerp is a little suspicious
-        Some(self.entries[idx - 1].lerp(&self.entries[idx], *t - self.entries[idx].time_tai()))
+        Some(idx)
     }
 
-    pub fn get_ut1(&self, t: &Epoch<UT1>) -> Option<Entry> {
-        let idx = self.entries.iter().position(|e| e.time_ut1() > *t)?;
+    // look up and interpolate an entry, along the time axis given by
+    // keys, one per entry
+    fn get_by<S>(&self, t: &Epoch<S>, keys: &[Epoch<S>]) -> Option<Entry> {
+        let idx = match Self::locate(t, keys) {
+            Some(idx) => idx,
+            None => return self.extrapolate(t, keys),
+        };
+        let (before, after) = (&self.entries[idx - 1], &self.entries[idx]);
+
+        // measure the weight on the same axis the query used
+        let span = (keys[idx] - keys[idx - 1]).to_seconds();
+        let g1 = if span > 0.0 {
+            (*t - keys[idx - 1]).to_seconds() / span
+        } else {
+            // duplicate entries, don't divide by zero
+            0.0
+        };
+
+        let hold = self.type_boundary == TypeBoundary::Hold;
+        if hold && before.data_type != after.data_type {
+            let observed = if before.is_observed() { before } else { after };
+            return Some(Entry {
+                time_utc: before.time_utc,
+                tai_utc: before.tai_utc,
+                ..observed.clone()
+            });
+        }
+
+        Some(match self.interpolation {
+            Interpolation::Nearest => {
+                let nearest = if g1 < 0.5 { before } else { after };
+                // keep UT1 - TAI from the nearest entry, so it still
+                // agrees with the TAI - UTC held from before
+                let ut1_tai = nearest.ut1_utc - nearest.tai_utc as f64;
+                Entry {
+                    time_utc: before.time_utc,
+                    tai_utc: before.tai_utc,
+                    ut1_utc: ut1_tai + before.tai_utc as f64,
+                    ..nearest.clone()
+                }
+            }
+            Interpolation::Linear => before.lerp(after, g1),
+            Interpolation::CubicSpline => {
+                // repeat the end samples at the edges of the data
+                let first = if idx >= 2 {
+                    &self.entries[idx - 2]
+                } else {
+                    before
+                };
+                let last = self.entries.get(idx + 1).unwrap_or(after);
+
+                // also repeat them at a held type boundary
+                let first = if hold && first.data_type != before.data_type {
+                    before
+                } else {
+                    first
+                };
+                let last = if hold && last.data_type != after.data_type {
+                    after
+                } else {
+                    last
+                };
+                before.cubic([first, before, after, last], g1)
+            }
+        })
+    }
+
+    // extrapolate an entry outside of the data, along the time axis
+    // given by key
+    fn extrapolate<S>(&self, t: &Epoch<S>, keys: &[Epoch<S>]) -> Option<Entry> {
+        // indices of the edge entry, and the one next to it
+        let len = keys.len();
+        let (edge, inner) = if t < keys.first()? {
+            (0, Some(1).filter(|&i| i < len))
+        } else {
+            (len - 1, len.checked_sub(2))
+        };
+        let (edge, edge_key) = (&self.entries[edge], keys[edge]);
+        let inner = inner.map(|i| (&self.entries[i], keys[i]));
 
-        if idx == 0 {
+        let mut entry = match self.extrapolation {
+            ExtrapolationPolicy::None => return None,
+            ExtrapolationPolicy::Clamp => edge.clone(),
+            ExtrapolationPolicy::Linear => {
+                let (inner, inner_key) = inner?;
+                let span = (edge_key - inner_key).to_seconds();
+                if span != 0.0 {
+                    inner.lerp(edge, (*t - inner_key).to_seconds() / span)
+                } else {
+                    edge.clone()
+                }
+            }
+        };
+
+        // leap seconds can't be predicted, hold the edge value, and
+        // keep UT1 - TAI as it was
+        entry.ut1_utc += (edge.tai_utc - entry.tai_utc) as f64;
+        entry.tai_utc = edge.tai_utc;
+        entry.time_utc = edge.time_utc;
+        entry.data_type = Type::Predicted;
+        Some(entry)
+    }
+
+    pub fn get_utc(&self, t: &Epoch<UTC>) -> Option<Entry> {
+        self.get_by(t, &self.times_utc)
+    }
+
+    /// Like [get_utc][Self::get_utc], but only if both samples used
+    /// are within `tolerance` of the query.
+    ///
+    /// This avoids interpolating across large gaps in the data.
+    pub fn get_utc_within(&self, t: &Epoch<UTC>, tolerance: TimeDelta<UTC>) -> Option<Entry> {
+        let idx = Self::locate(t, &self.times_utc)?;
+
+        if *t - self.entries[idx - 1].time_utc > tolerance
+            || self.entries[idx].time_utc - *t > tolerance
+        {
             return None;
         }
 
-        // FIXME this lerp is *extremely* suspicious
-        Some(self.entries[idx - 1].lerp(&self.entries[idx], *t - self.entries[idx].time_ut1()))
+        self.get_utc(t)
+    }
+
+    /// Like [get_utc][Self::get_utc], but also report which entries
+    /// were used and how far between them the query is.
+    ///
+    /// This can be used to reject stale or extrapolated data.
+    pub fn get_utc_detailed(&self, t: &Epoch<UTC>) -> Option<Interpolated> {
+        let entry = self.get_utc(t)?;
+
+        let (before, after) = match Self::locate(t, &self.times_utc) {
+            Some(idx) => (&self.entries[idx - 1], &self.entries[idx]),
+            // outside of the data, measure from the edge entries
+            None if *t < self.entries[0].time_utc => (
+                &self.entries[0],
+                self.entries.get(1).unwrap_or(&self.entries[0]),
+            ),
+            None => {
+                let len = self.entries.len();
+                (&self.entries[len.saturating_sub(2)], &self.entries[len - 1])
+            }
+        };
+
+        let span = (after.time_utc - before.time_utc).to_seconds();
+        let fraction = if span != 0.0 {
+            (*t - before.time_utc).to_seconds() / span
+        } else {
+            0.0
+        };
+
+        Some(Interpolated {
+            entry,
+            fraction,
+            bracketing_times: (before.time_utc, after.time_utc),
+        })
+    }
+
+    pub fn get_tai(&self, t: &Epoch<TAI>) -> Option<Entry> {
+        self.get_by(t, &self.times_tai)
+    }
+
+    pub fn get_ut1(&self, t: &Epoch<UT1>) -> Option<Entry> {
+        self.get_by(t, &self.times_ut1)
+    }
+
+    /// Return UT1 - UTC for the given UTC [Epoch], along with a
+    /// 1-sigma estimate of the interpolation error in seconds.
+    ///
+    /// The error is estimated from the curvature of UT1 - TAI near
+    /// the query, and grows towards the middle of wide gaps between
+    /// samples. Celestrak data has no per-sample uncertainties, so
+    /// those are not included.
+    pub fn ut1_utc_with_uncertainty(&self, t: &Epoch<UTC>) -> Option<(TimeDelta<UT1>, f64)> {
+        let idx = Self::locate(t, &self.times_utc)?;
+
+        let value = TimeDelta::from_seconds(self.get_utc(t)?.ut1_utc);
+
+        // pick three neighboring samples to estimate curvature
+        let first = if idx >= 2 {
+            idx - 2
+        } else if idx + 1 < self.entries.len() {
+            idx - 1
+        } else {
+            // only two samples, no way to estimate curvature
+            return Some((value, 0.0));
+        };
+        let [e0, e1, e2] = [0, 1, 2].map(|i| &self.entries[first + i]);
+
+        // UT1 - TAI is smooth across leap seconds, UT1 - UTC is not
+        let f = |e: &Entry| e.ut1_utc - e.tai_utc as f64;
+        let h01 = (e1.time_utc - e0.time_utc).to_seconds();
+        let h12 = (e2.time_utc - e1.time_utc).to_seconds();
+        let curvature = 2.0 * ((f(e2) - f(e1)) / h12 - (f(e1) - f(e0)) / h01) / (h01 + h12);
+
+        // error bound for linear interpolation
+        let before = (*t - self.entries[idx - 1].time_utc).to_seconds();
+        let after = (self.entries[idx].time_utc - *t).to_seconds();
+        Some((value, 0.5 * curvature.abs() * before * after))
+    }
+}
+
+/// Builds a [CelestrakProvider] one [Entry] at a time.
+///
+/// Use [CelestrakProvider::builder] to create one.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    entries: Vec<Entry>,
+}
+
+impl Builder {
+    /// Add an observed entry with the given time, TAI - UTC, and
+    /// UT1 - UTC. All other parameters are zero. See [Entry::new].
+    pub fn entry(self, time_utc: Epoch<UTC>, tai_utc: i64, ut1_utc: f64) -> Self {
+        self.push(Entry::new(time_utc, tai_utc, ut1_utc))
+    }
+
+    /// Add a complete [Entry].
+    pub fn push(mut self, entry: Entry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Build the provider. Entries may have been added in any order.
+    pub fn build(self) -> CelestrakProvider {
+        CelestrakProvider::from_entries(self.entries)
     }
 }
 
 impl Entry {
+    /// An observed entry with the given time, TAI - UTC, and
+    /// UT1 - UTC.
+    ///
+    /// All other parameters are zero. This is mostly useful for tests
+    /// and examples.
+    pub fn new(time_utc: Epoch<UTC>, tai_utc: i64, ut1_utc: f64) -> Self {
+        Self {
+            time_utc,
+            x: Arcsec(0.0),
+            y: Arcsec(0.0),
+            ut1_utc,
+            lod: 0.0,
+            dpsi: Arcsec(0.0),
+            deps: Arcsec(0.0),
+            dx: Arcsec(0.0),
+            dy: Arcsec(0.0),
+            tai_utc,
+            data_type: Type::Observed,
+        }
+    }
+
     pub fn time_tai(&self) -> Epoch<TAI> {
         self.time_utc.transmute() + TimeDelta::new(self.tai_utc, 0).unwrap()
     }
@@ -196,8 +888,27 @@ impl Entry {
         self.time_utc.transmute() + TimeDelta::from_seconds(self.ut1_utc)
     }
 
-    fn lerp<S>(&self, other: &Self, t: TimeDelta<S>) -> Self {
-        let g1 = t.to_seconds() / (other.time_utc - self.time_utc).to_seconds();
+    /// Is this entry observed, rather than predicted?
+    pub fn is_observed(&self) -> bool {
+        self.data_type == Type::Observed
+    }
+
+    /// The Earth orientation parameters in this entry.
+    pub fn state(&self) -> EopState {
+        EopState {
+            x: self.x,
+            y: self.y,
+            ut1_utc: self.ut1_utc,
+            lod: self.lod,
+            dpsi: self.dpsi,
+            deps: self.deps,
+            dx: self.dx,
+            dy: self.dy,
+        }
+    }
+
+    // g1 is the weight of other, from 0.0 to 1.0
+    fn lerp(&self, other: &Self, g1: f64) -> Self {
         let g0 = 1.0 - g1;
 
         Self {
@@ -209,16 +920,57 @@ impl Entry {
             // interpolate
             x: g0 * self.x + g1 * other.x,
             y: g0 * self.y + g1 * other.y,
-            ut1_utc: g0 * self.ut1_utc + g1 * other.ut1_utc,
-            lod: g0 * self.lod + g1 * other.lod,
-            dpsi: g0 * self.dpsi + g1 * other.dpsi,
-            deps: g0 * self.deps + g1 * other.deps,
-            dx: g0 * self.dx + g1 * other.dx,
-            dy: g0 * self.dy + g1 * other.dy,
+            // UT1 - TAI is smooth across leap seconds, UT1 - UTC is not
+            ut1_utc: g0 * (self.ut1_utc - self.tai_utc as f64)
+                + g1 * (other.ut1_utc - other.tai_utc as f64)
+                + self.tai_utc as f64,
+
+            // these may be blank in predicted rows
+            lod: lerp_known(self.lod, other.lod, g1),
+            dpsi: Arcsec(lerp_known(self.dpsi.0, other.dpsi.0, g1)),
+            deps: Arcsec(lerp_known(self.deps.0, other.deps.0, g1)),
+            dx: Arcsec(lerp_known(self.dx.0, other.dx.0, g1)),
+            dy: Arcsec(lerp_known(self.dy.0, other.dy.0, g1)),
 
             data_type: self.data_type.merge(&other.data_type),
         }
     }
+
+    // points are the four surrounding entries, self is points[1]
+    // g1 is the weight of points[2], from 0.0 to 1.0
+    fn cubic(&self, points: [&Self; 4], g1: f64) -> Self {
+        let spline = |f: fn(&Self) -> f64| catmull_rom(points.map(f), g1);
+
+        Self {
+            // same as lerp
+            tai_utc: self.tai_utc,
+            time_utc: self.time_utc,
+
+            x: Arcsec(spline(|e| e.x.0)),
+            y: Arcsec(spline(|e| e.y.0)),
+            ut1_utc: spline(|e| e.ut1_utc - e.tai_utc as f64) + self.tai_utc as f64,
+            lod: spline(|e| e.lod),
+            dpsi: Arcsec(spline(|e| e.dpsi.0)),
+            deps: Arcsec(spline(|e| e.deps.0)),
+            dx: Arcsec(spline(|e| e.dx.0)),
+            dy: Arcsec(spline(|e| e.dy.0)),
+
+            data_type: points[1].data_type.merge(&points[2].data_type),
+        }
+    }
+}
+
+// Catmull-Rom spline through p, evaluated between p[1] and p[2]
+fn catmull_rom(p: [f64; 4], g1: f64) -> f64 {
+    if p.iter().any(|v| v.is_nan()) {
+        return lerp_known(p[1], p[2], g1);
+    }
+
+    let a = 2.0 * p[1];
+    let b = p[2] - p[0];
+    let c = 2.0 * p[0] - 5.0 * p[1] + 4.0 * p[2] - p[3];
+    let d = -p[0] + 3.0 * p[1] - 3.0 * p[2] + p[3];
+    0.5 * (a + g1 * (b + g1 * (c + g1 * d)))
 }
 
 impl Type {
@@ -234,6 +986,22 @@ impl Type {
     }
 }
 
+impl Type {
+    /// The code used for this type in Celestrak files, `O` or `P`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Observed => "O",
+            Self::Predicted => "P",
+        }
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::str::FromStr for Type {
     type Err = ();
 
@@ -246,6 +1014,15 @@ impl std::str::FromStr for Type {
     }
 }
 
+impl<'a> IntoIterator for &'a CelestrakProvider {
+    type Item = &'a Entry;
+    type IntoIter = std::slice::Iter<'a, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl super::Provider for CelestrakProvider {
     fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
         TimeDelta::new(self.get_utc(epoch)?.tai_utc, 0)
@@ -262,4 +1039,815 @@ impl super::Provider for CelestrakProvider {
     fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
         Some(TimeDelta::from_seconds(self.get_ut1(epoch)?.ut1_utc))
     }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        let entry = self.get_utc(epoch)?;
+        Some((entry.x, entry.y))
+    }
+
+    // blank LOD values are read as NaN, so treat them as missing
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        let entry = self.get_utc(epoch)?;
+        Some(entry.lod).filter(|lod| !lod.is_nan())
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        let entry = self.get_utc(epoch)?;
+        Some((entry.dpsi, entry.deps))
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.leap_seconds_iter().collect()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        Box::new(
+            self.entries
+                .windows(2)
+                .filter(|pair| pair[0].tai_utc != pair[1].tai_utc)
+                .filter_map(|pair| Some((pair[1].time_utc, TimeDelta::new(pair[1].tai_utc, 0)?))),
+        )
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        Some(self.get_utc(epoch)?.state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups_agree_on_every_axis() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.42),
+            (57002.0, 35, -0.45),
+            (57003.0, 35, -0.47),
+        ]);
+
+        for i in 0..30 {
+            let t_utc = Epoch::<UTC>::from_mjd(57000.0 + i as f64 * 0.1);
+            let entry = provider.get_utc(&t_utc).unwrap();
+            let t_tai = t_utc.to_tai_with(&provider).unwrap();
+            let t_ut1 = t_utc.to_ut1_with(&provider).unwrap();
+
+            for other in [provider.get_tai(&t_tai), provider.get_ut1(&t_ut1)] {
+                let other = other.unwrap();
+                assert_eq!(other.time_utc, entry.time_utc);
+                assert_eq!(other.tai_utc, entry.tai_utc);
+                assert!((other.ut1_utc - entry.ut1_utc).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn binary_search_matches_linear_scan() {
+        // 50,000 daily entries, with a leap second every 5,000 days
+        let rows: Vec<(f64, i64, f64)> = (0..50_000)
+            .map(|i| {
+                (
+                    20_000.0 + i as f64,
+                    10 + i / 5_000,
+                    0.4 - (i % 5_000) as f64 * 1e-4,
+                )
+            })
+            .collect();
+        let provider = CelestrakProvider::from_rows(&rows);
+
+        fn linear<T: Ord>(t: &T, keys: &[T]) -> Option<usize> {
+            keys.iter().position(|k| k > t).filter(|&idx| idx > 0)
+        }
+
+        for i in (-10..50_000).step_by(499).chain([49_998, 49_999, 50_005]) {
+            for frac in [0.0, 0.25, 0.5] {
+                let t_utc = Epoch::<UTC>::from_mjd(20_000.0 + i as f64 + frac);
+                let idx = linear(&t_utc, &provider.times_utc);
+                assert_eq!(CelestrakProvider::locate(&t_utc, &provider.times_utc), idx);
+
+                let t_tai: Epoch<TAI> = t_utc.transmute();
+                let t_ut1: Epoch<UT1> = t_utc.transmute();
+                assert_eq!(
+                    CelestrakProvider::locate(&t_tai, &provider.times_tai),
+                    linear(&t_tai, &provider.times_tai)
+                );
+                assert_eq!(
+                    CelestrakProvider::locate(&t_ut1, &provider.times_ut1),
+                    linear(&t_ut1, &provider.times_ut1)
+                );
+
+                // and the lookup uses the entries either side
+                if let Some(idx) = idx {
+                    let entry = provider.get_utc(&t_utc).unwrap();
+                    let (before, after) = (&provider.entries[idx - 1], &provider.entries[idx]);
+                    let expected = before.ut1_utc + frac * (after.ut1_utc - before.ut1_utc);
+                    if before.tai_utc == after.tai_utc {
+                        assert!((entry.ut1_utc - expected).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lookups_need_surrounding_entries() {
+        let provider = CelestrakProvider::from_rows(&[(57000.0, 35, 0.0), (57001.0, 35, 0.0)]);
+        assert!(provider.get_utc(&Epoch::from_mjd(56999.9)).is_none());
+        assert!(provider.get_utc(&Epoch::from_mjd(57000.0)).is_some());
+        assert!(provider.get_utc(&Epoch::from_mjd(57001.0)).is_none());
+        assert!(provider.get_tai(&Epoch::from_mjd(57000.0)).is_none());
+        assert!(provider.get_ut1(&Epoch::from_mjd(57000.5)).is_some());
+    }
+
+    #[test]
+    fn linear_weights_on_every_axis() {
+        let provider = CelestrakProvider::builder()
+            .push(Entry {
+                x: Arcsec(0.1),
+                lod: 0.001,
+                ..Entry::new(Epoch::from_mjd(57000.0), 35, -0.4)
+            })
+            .push(Entry {
+                x: Arcsec(0.3),
+                lod: 0.003,
+                ..Entry::new(Epoch::from_mjd(57001.0), 35, -0.6)
+            })
+            .push(Entry::new(Epoch::from_mjd(57002.0), 35, -0.8))
+            .build();
+
+        // a quarter of the way from the first entry to the second
+        let t_utc = Epoch::<UTC>::from_mjd(57000.25);
+        let t_tai: Epoch<TAI> = t_utc.transmute() + TimeDelta::new(35, 0).unwrap();
+        let t_ut1: Epoch<UT1> = t_utc.transmute() - TimeDelta::from_seconds(0.45);
+
+        for entry in [
+            provider.get_utc(&t_utc),
+            provider.get_tai(&t_tai),
+            provider.get_ut1(&t_ut1),
+        ] {
+            let entry = entry.unwrap();
+            assert!((entry.ut1_utc + 0.45).abs() < 1e-6);
+            assert!((entry.x.0 - 0.15).abs() < 1e-6);
+            assert!((entry.lod - 0.0015).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn nearest_and_linear_at_midpoint() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.50),
+            (57002.0, 35, -0.60),
+        ]);
+        let nearest = provider.clone().with_interpolation(Interpolation::Nearest);
+
+        let before = Epoch::<UTC>::from_mjd(57000.49);
+        let after = Epoch::<UTC>::from_mjd(57000.51);
+        assert!((provider.get_utc(&before).unwrap().ut1_utc + 0.449).abs() < 1e-9);
+        assert!((provider.get_utc(&after).unwrap().ut1_utc + 0.451).abs() < 1e-9);
+        assert!((nearest.get_utc(&before).unwrap().ut1_utc + 0.40).abs() < 1e-9);
+        assert!((nearest.get_utc(&after).unwrap().ut1_utc + 0.50).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cubic_passes_through_samples() {
+        let rows = [
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.43),
+            (57002.0, 35, -0.41),
+            (57003.0, 35, -0.47),
+            (57004.0, 35, -0.44),
+        ];
+        let provider =
+            CelestrakProvider::from_rows(&rows).with_interpolation(Interpolation::CubicSpline);
+
+        // the last row can't be looked up, it has nothing after it
+        for &(mjd, _, ut1_utc) in &rows[..rows.len() - 1] {
+            let entry = provider.get_utc(&Epoch::from_mjd(mjd)).unwrap();
+            assert!((entry.ut1_utc - ut1_utc).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn interpolation_across_leap_second() {
+        // UT1 - TAI is -36.6, -36.7, -36.8, -36.9 throughout
+        let rows = [
+            (57752.0, 36, -0.6),
+            (57753.0, 36, -0.7),
+            (57754.0, 37, 0.2),
+            (57755.0, 37, 0.1),
+        ];
+
+        for interpolation in [
+            Interpolation::Nearest,
+            Interpolation::Linear,
+            Interpolation::CubicSpline,
+        ] {
+            let provider = CelestrakProvider::from_rows(&rows).with_interpolation(interpolation);
+            for i in 1..10 {
+                let t = Epoch::<UTC>::from_mjd(57753.0 + i as f64 / 10.0);
+                let entry = provider.get_utc(&t).unwrap();
+                assert_eq!(entry.tai_utc, 36);
+
+                // the held TAI - UTC and UT1 - UTC must agree
+                let ut1_tai = entry.ut1_utc - entry.tai_utc as f64;
+                assert!((-36.8..=-36.7).contains(&ut1_tai), "{interpolation:?}");
+            }
+
+            let t = Epoch::<UTC>::from_mjd(57754.0);
+            assert_eq!(provider.get_utc(&t).unwrap().tai_utc, 37);
+            assert!((provider.get_utc(&t).unwrap().ut1_utc - 0.2).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn extrapolation_policies() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.50),
+            (57002.0, 36, 0.40),
+        ]);
+        let before = Epoch::<UTC>::from_mjd(56999.0);
+        let after = Epoch::<UTC>::from_mjd(57003.0);
+
+        assert!(provider.get_utc(&before).is_none());
+        assert!(provider.get_utc(&after).is_none());
+
+        let clamp = provider
+            .clone()
+            .with_extrapolation(ExtrapolationPolicy::Clamp);
+        let entry = clamp.get_utc(&before).unwrap();
+        assert!((entry.ut1_utc + 0.40).abs() < 1e-9);
+        assert_eq!(entry.data_type, Type::Predicted);
+        let entry = clamp.get_utc(&after).unwrap();
+        assert!((entry.ut1_utc - 0.40).abs() < 1e-9);
+        assert_eq!(entry.tai_utc, 36);
+
+        let linear = provider.with_extrapolation(ExtrapolationPolicy::Linear);
+        let entry = linear.get_utc(&before).unwrap();
+        assert!((entry.ut1_utc + 0.30).abs() < 1e-9);
+        assert_eq!(entry.tai_utc, 35);
+        assert_eq!(entry.data_type, Type::Predicted);
+
+        // TAI - UTC is held at the edge, never extrapolated
+        let entry = linear.get_utc(&after).unwrap();
+        assert_eq!(entry.tai_utc, 36);
+        assert!((entry.ut1_utc - 0.30).abs() < 1e-9);
+        assert_eq!(entry.time_utc, Epoch::from_mjd(57002.0));
+    }
+
+    #[test]
+    fn get_utc_detailed() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.50),
+            (57002.0, 35, -0.60),
+        ]);
+        let mjd = |mjd| Epoch::<UTC>::from_mjd(mjd);
+
+        let detailed = provider.get_utc_detailed(&mjd(57001.25)).unwrap();
+        assert!((detailed.fraction - 0.25).abs() < 1e-9);
+        assert_eq!(detailed.bracketing_times, (mjd(57001.0), mjd(57002.0)));
+        assert!((detailed.entry.ut1_utc + 0.525).abs() < 1e-9);
+
+        // no extrapolation, no entry
+        assert!(provider.get_utc_detailed(&mjd(57003.0)).is_none());
+
+        // extrapolated fractions fall outside [0, 1]
+        let linear = provider.with_extrapolation(ExtrapolationPolicy::Linear);
+        let detailed = linear.get_utc_detailed(&mjd(57003.0)).unwrap();
+        assert!((detailed.fraction - 2.0).abs() < 1e-9);
+        assert_eq!(detailed.bracketing_times, (mjd(57001.0), mjd(57002.0)));
+        let detailed = linear.get_utc_detailed(&mjd(56999.0)).unwrap();
+        assert!((detailed.fraction + 1.0).abs() < 1e-9);
+        assert_eq!(detailed.bracketing_times, (mjd(57000.0), mjd(57001.0)));
+    }
+
+    #[test]
+    fn type_boundary() {
+        let provider = CelestrakProvider::builder()
+            .entry(Epoch::from_mjd(57000.0), 35, -0.40)
+            .push(Entry {
+                data_type: Type::Predicted,
+                ..Entry::new(Epoch::from_mjd(57001.0), 35, -0.50)
+            })
+            .push(Entry {
+                data_type: Type::Predicted,
+                ..Entry::new(Epoch::from_mjd(57002.0), 35, -0.60)
+            })
+            .build();
+        let t = Epoch::<UTC>::from_mjd(57000.75);
+
+        let entry = provider.get_utc(&t).unwrap();
+        assert!((entry.ut1_utc + 0.475).abs() < 1e-9);
+        assert_eq!(entry.data_type, Type::Predicted);
+
+        let held = provider.with_type_boundary(TypeBoundary::Hold);
+        let entry = held.get_utc(&t).unwrap();
+        assert!((entry.ut1_utc + 0.40).abs() < 1e-9);
+        assert_eq!(entry.data_type, Type::Observed);
+
+        // between two predicted entries, hold changes nothing
+        let entry = held.get_utc(&Epoch::from_mjd(57001.5)).unwrap();
+        assert!((entry.ut1_utc + 0.55).abs() < 1e-9);
+    }
+
+    #[test]
+    fn merge() {
+        let predicted = |mjd, ut1_utc| Entry {
+            data_type: Type::Predicted,
+            ..Entry::new(Epoch::from_mjd(mjd), 35, ut1_utc)
+        };
+        let archive = CelestrakProvider::builder()
+            .entry(Epoch::from_mjd(57000.0), 35, -0.40)
+            .entry(Epoch::from_mjd(57001.0), 35, -0.50)
+            .push(predicted(57002.0, -0.60))
+            .build()
+            .with_extrapolation(ExtrapolationPolicy::Clamp);
+        let fresh = CelestrakProvider::builder()
+            .push(predicted(57001.0, -0.55))
+            .entry(Epoch::from_mjd(57002.0), 35, -0.65)
+            .push(predicted(57003.0, -0.70))
+            .build();
+        let ut1_utc = |provider: &CelestrakProvider| -> Vec<f64> {
+            provider.entries().iter().map(|e| e.ut1_utc).collect()
+        };
+
+        // observed data wins, whichever provider it is in
+        let merged =
+            CelestrakProvider::merge(archive.clone(), fresh.clone(), MergeRule::PreferObserved);
+        assert_eq!(ut1_utc(&merged), [-0.40, -0.50, -0.65, -0.70]);
+        assert!(merged
+            .entries()
+            .windows(2)
+            .all(|w| w[0].time_utc < w[1].time_utc));
+
+        let merged =
+            CelestrakProvider::merge(archive.clone(), fresh.clone(), MergeRule::PreferFirst);
+        assert_eq!(ut1_utc(&merged), [-0.40, -0.50, -0.60, -0.70]);
+
+        // settings come from the first provider
+        assert!(merged.get_utc(&Epoch::from_mjd(57005.0)).is_some());
+        let merged = CelestrakProvider::merge(fresh, archive, MergeRule::default());
+        assert!(merged.get_utc(&Epoch::from_mjd(57005.0)).is_none());
+        assert_eq!(ut1_utc(&merged), [-0.40, -0.50, -0.65, -0.70]);
+    }
+
+    #[test]
+    fn merge_duplicates_within_one() {
+        let predicted = |mjd, ut1_utc| Entry {
+            data_type: Type::Predicted,
+            ..Entry::new(Epoch::from_mjd(mjd), 35, ut1_utc)
+        };
+        // two entries at 57001 in the archive alone
+        let archive = CelestrakProvider::builder()
+            .entry(Epoch::from_mjd(57000.0), 35, -0.40)
+            .push(predicted(57001.0, -0.45))
+            .entry(Epoch::from_mjd(57001.0), 35, -0.50)
+            .build();
+        let fresh = CelestrakProvider::builder()
+            .push(predicted(57001.0, -0.55))
+            .push(predicted(57002.0, -0.60))
+            .push(predicted(57002.0, -0.65))
+            .build();
+        let ut1_utc = |provider: &CelestrakProvider| -> Vec<f64> {
+            provider.entries().iter().map(|e| e.ut1_utc).collect()
+        };
+
+        let merged =
+            CelestrakProvider::merge(archive.clone(), fresh.clone(), MergeRule::PreferObserved);
+        assert_eq!(ut1_utc(&merged), [-0.40, -0.50, -0.60]);
+        let merged = CelestrakProvider::merge(archive, fresh, MergeRule::PreferFirst);
+        assert_eq!(ut1_utc(&merged), [-0.40, -0.45, -0.60]);
+        assert!(merged
+            .entries()
+            .windows(2)
+            .all(|w| w[0].time_utc < w[1].time_utc));
+    }
+
+    #[test]
+    fn polar_motion_and_nutation() {
+        use crate::provider::{EmptyProvider, Provider};
+
+        let provider = CelestrakProvider::builder()
+            .push(Entry {
+                x: Arcsec(0.1),
+                y: Arcsec(0.3),
+                dpsi: Arcsec(-0.05),
+                deps: Arcsec(0.01),
+                ..Entry::new(Epoch::from_mjd(57000.0), 35, -0.4)
+            })
+            .push(Entry {
+                x: Arcsec(0.2),
+                y: Arcsec(0.5),
+                dpsi: Arcsec(-0.07),
+                deps: Arcsec(0.03),
+                ..Entry::new(Epoch::from_mjd(57001.0), 35, -0.4)
+            })
+            .build();
+
+        let t = Epoch::<UTC>::from_mjd(57000.5);
+        let (x, y) = provider.polar_motion_for_utc(&t).unwrap();
+        assert!((x.0 - 0.15).abs() < 1e-9 && (y.0 - 0.4).abs() < 1e-9);
+        let (dpsi, deps) = provider.nutation_corrections_for_utc(&t).unwrap();
+        assert!((dpsi.0 + 0.06).abs() < 1e-9 && (deps.0 - 0.02).abs() < 1e-9);
+
+        let outside = Epoch::<UTC>::from_mjd(56999.0);
+        assert_eq!(provider.polar_motion_for_utc(&outside), None);
+        assert_eq!(provider.nutation_corrections_for_utc(&outside), None);
+        assert_eq!(EmptyProvider.polar_motion_for_utc(&t), None);
+    }
+
+    #[test]
+    fn lod_for_utc() {
+        use crate::provider::Provider;
+
+        let lod = |mjd, lod| Entry {
+            lod,
+            ..Entry::new(Epoch::from_mjd(mjd), 35, -0.4)
+        };
+        let provider = CelestrakProvider::builder()
+            .push(lod(57000.0, 0.001))
+            .push(lod(57001.0, 0.003))
+            .push(lod(57002.0, f64::NAN))
+            .push(lod(57003.0, f64::NAN))
+            .build();
+
+        let at = |mjd| provider.lod_for_utc(&Epoch::from_mjd(mjd));
+        assert!((at(57000.5).unwrap() - 0.002).abs() < 1e-12);
+
+        // blank values are skipped, or missing if there is nothing else
+        assert_eq!(at(57001.5), Some(0.003));
+        assert_eq!(at(57002.5), None);
+        assert_eq!(at(56999.0), None);
+    }
+
+    #[test]
+    #[cfg(feature = "download")]
+    fn download_bad_url() {
+        // fails before touching the network
+        let err = CelestrakProvider::from_url("not a url").unwrap_err();
+        assert!(matches!(err, Error::Http(ref e) if e.is_builder()));
+    }
+
+    #[test]
+    fn leap_seconds_list() {
+        use crate::provider::Provider;
+
+        let provider = CelestrakProvider::from_rows(&[
+            (57203.0, 35, 0.0),
+            (57204.0, 36, 0.0),
+            (57205.0, 36, 0.0),
+            (57753.0, 36, 0.0),
+            (57754.0, 37, 0.0),
+        ]);
+        let expected = [
+            (Epoch::from_mjd(57204.0), TimeDelta::new(36, 0).unwrap()),
+            (Epoch::from_mjd(57754.0), TimeDelta::new(37, 0).unwrap()),
+        ];
+        assert_eq!(provider.leap_seconds(), expected);
+        assert!(provider.leap_seconds_iter().eq(expected));
+
+        let flat = CelestrakProvider::from_rows(&[(57000.0, 35, 0.0), (57001.0, 35, 0.0)]);
+        assert!(flat.leap_seconds().is_empty());
+    }
+
+    #[test]
+    fn accessors_and_coverage() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, 0.1),
+            (57001.0, 35, 0.2),
+            (57002.0, 35, 0.3),
+            (57003.0, 35, 0.4),
+        ]);
+        assert_eq!(provider.entries().len(), 4);
+        assert_eq!(provider.iter().count(), 4);
+        assert_eq!((&provider).into_iter().last().unwrap().ut1_utc, 0.4);
+        assert_eq!(
+            provider.coverage(),
+            Some((Epoch::from_mjd(57000.0), Epoch::from_mjd(57003.0)))
+        );
+
+        // inclusive on both ends
+        let between =
+            provider.entries_between(&Epoch::from_mjd(57001.0), &Epoch::from_mjd(57002.0));
+        assert_eq!(between.len(), 2);
+        assert_eq!(between[0].ut1_utc, 0.2);
+        let between =
+            provider.entries_between(&Epoch::from_mjd(57000.5), &Epoch::from_mjd(57000.7));
+        assert!(between.is_empty());
+        let between =
+            provider.entries_between(&Epoch::from_mjd(57002.0), &Epoch::from_mjd(57001.0));
+        assert!(between.is_empty());
+
+        assert_eq!(CelestrakProvider::from_rows(&[]).coverage(), None);
+    }
+
+    #[test]
+    fn observed_only() {
+        let predicted = |mjd| Entry {
+            data_type: Type::Predicted,
+            ..Entry::new(Epoch::from_mjd(mjd), 35, 0.0)
+        };
+        let provider = CelestrakProvider::builder()
+            .push(Entry::new(Epoch::from_mjd(57000.0), 35, 0.0))
+            .push(Entry::new(Epoch::from_mjd(57001.0), 35, 0.0))
+            .push(predicted(57002.0))
+            .push(predicted(57003.0))
+            .build()
+            .with_interpolation(Interpolation::Nearest);
+
+        let at = |mjd| Epoch::<UTC>::from_mjd(mjd);
+        assert_eq!(
+            provider.data_type_for_utc(&at(57000.5)),
+            Some(Type::Observed)
+        );
+        assert_eq!(
+            provider.data_type_for_utc(&at(57001.75)),
+            Some(Type::Predicted)
+        );
+        assert_eq!(
+            provider.data_type_for_utc(&at(57002.5)),
+            Some(Type::Predicted)
+        );
+        assert_eq!(provider.data_type_for_utc(&at(56999.0)), None);
+        assert!(provider.entries()[0].is_observed());
+        assert!(!provider.entries()[2].is_observed());
+
+        // settings carry over
+        let observed = provider.observed_only();
+        assert_eq!(observed.entries().len(), 2);
+        assert!(observed.iter().all(Entry::is_observed));
+        assert_eq!(observed.coverage().unwrap().1, Epoch::from_mjd(57001.0));
+        assert_eq!(observed.interpolation, Interpolation::Nearest);
+    }
+
+    #[test]
+    fn checked_entries() {
+        let entry = |mjd, ut1_utc| Entry::new(Epoch::from_mjd(mjd), 35, ut1_utc);
+
+        let provider =
+            CelestrakProvider::from_entries_checked(vec![entry(57000.0, 0.1), entry(57001.0, 0.2)]);
+        assert_eq!(provider.unwrap().entries().len(), 2);
+
+        assert!(matches!(
+            CelestrakProvider::from_entries_checked(vec![
+                entry(57000.0, 0.1),
+                entry(57001.0, 0.2),
+                entry(57001.0, 0.3),
+            ]),
+            Err(Error::DuplicateEpoch(2))
+        ));
+        assert!(matches!(
+            CelestrakProvider::from_entries_checked(vec![entry(57001.0, 0.2), entry(57000.0, 0.1)]),
+            Err(Error::NonMonotonic(1))
+        ));
+
+        // the lenient constructor sorts, and keeps duplicates
+        let provider = CelestrakProvider::from_entries(vec![
+            entry(57002.0, 0.3),
+            entry(57000.0, 0.1),
+            entry(57001.0, 0.2),
+            entry(57001.0, 0.2),
+        ]);
+        assert!(provider.iter().map(|e| e.time_utc).is_sorted());
+        for mjd in [57000.5, 57001.0, 57001.5] {
+            let entry = provider.get_utc(&Epoch::from_mjd(mjd)).unwrap();
+            assert!(entry.ut1_utc.is_finite());
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let provider = CelestrakProvider::builder()
+            .push(Entry {
+                x: Arcsec(0.1),
+                y: Arcsec(0.25),
+                lod: 0.0012,
+                ..Entry::new(Epoch::from_mjd(57000.0), 35, -0.40)
+            })
+            .push(Entry {
+                lod: f64::NAN,
+                dpsi: Arcsec::NAN,
+                data_type: Type::Predicted,
+                ..Entry::new(Epoch::from_mjd(57001.0), 35, -0.5)
+            })
+            .build();
+
+        let mut csv = Vec::new();
+        provider.to_csv(&mut csv).unwrap();
+        let back = CelestrakProvider::from_csv(&csv[..]).unwrap();
+
+        assert_eq!(back.entries().len(), 2);
+        for (a, b) in provider.iter().zip(back.iter()) {
+            assert_eq!(a.time_utc, b.time_utc);
+            assert_eq!((a.x, a.y, a.ut1_utc), (b.x, b.y, b.ut1_utc));
+            assert_eq!(a.tai_utc, b.tai_utc);
+            assert_eq!(a.data_type, b.data_type);
+            assert_eq!(a.lod.is_nan(), b.lod.is_nan());
+            assert_eq!(a.dpsi.is_nan(), b.dpsi.is_nan());
+        }
+    }
+
+    #[test]
+    fn csv_blank_fields() {
+        let csv = "\
+DATE,MJD,X,Y,UT1-UTC,LOD,DPSI,DEPS,DX,DY,DAT,DATA_TYPE
+2024-06-01,60462,0.1,0.4,0.01,0.0010,-0.1,-0.01,0.0002,0.0001,37,O
+2024-06-02,60463,0.1,0.4,0.02,,,,,,37,P
+2024-06-03,60464,0.1,0.4,0.03,,,,,,37,P
+";
+        let provider = CelestrakProvider::from_csv(csv.as_bytes()).unwrap();
+        let entries = provider.entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].lod, 0.001);
+        assert!(entries[1].lod.is_nan());
+        assert!(entries[1].dpsi.is_nan() && entries[1].deps.is_nan());
+        assert!(entries[1].dx.is_nan() && entries[1].dy.is_nan());
+
+        // interpolation uses whichever side is known
+        let entry = provider.get_utc(&Epoch::from_mjd(60462.5)).unwrap();
+        assert_eq!(entry.lod, 0.001);
+        assert_eq!(entry.dpsi, Arcsec(-0.1));
+        assert!((entry.ut1_utc - 0.015).abs() < 1e-9);
+        let entry = provider.get_utc(&Epoch::from_mjd(60463.5)).unwrap();
+        assert!(entry.lod.is_nan());
+
+        // but UT1 - UTC is still required
+        let csv = csv.replace("0.02,,", ",,");
+        assert!(matches!(
+            CelestrakProvider::from_csv(csv.as_bytes()),
+            Err(Error::BadParse(1, "UT1-UTC"))
+        ));
+    }
+
+    #[test]
+    fn csv_limited() {
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, -0.40),
+            (57001.0, 35, -0.41),
+            (57002.0, 35, -0.42),
+        ]);
+        let mut csv = Vec::new();
+        provider.to_csv(&mut csv).unwrap();
+
+        let back = CelestrakProvider::from_csv_limited(&csv[..], 3, 1024).unwrap();
+        assert_eq!(back.entries().len(), 3);
+
+        assert!(matches!(
+            CelestrakProvider::from_csv_limited(&csv[..], 2, 1024),
+            Err(Error::LimitExceeded("entries"))
+        ));
+        assert!(matches!(
+            CelestrakProvider::from_csv_limited(&csv[..], 3, 16),
+            Err(Error::LimitExceeded("line length"))
+        ));
+    }
+
+    #[test]
+    fn csv_crlf() {
+        let provider = CelestrakProvider::from_rows(&[(57000.0, 35, -0.40), (57001.0, 35, -0.41)]);
+        let mut csv = Vec::new();
+        provider.to_csv(&mut csv).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        let crlf = text.replace('\n', "\r\n");
+
+        // the longest line fits exactly, not counting the \r\n
+        let longest = text.lines().map(str::len).max().unwrap();
+        let back =
+            CelestrakProvider::from_csv_limited(crlf.as_bytes(), usize::MAX, longest).unwrap();
+        assert_eq!(back.entries().len(), 2);
+        assert_eq!(back.entries()[1].data_type, provider.entries()[1].data_type);
+        assert_eq!(back.entries()[1].ut1_utc, -0.41);
+
+        assert!(matches!(
+            CelestrakProvider::from_csv_limited(crlf.as_bytes(), usize::MAX, longest - 1),
+            Err(Error::LimitExceeded("line length"))
+        ));
+    }
+
+    #[test]
+    fn csv_bad_utf8() {
+        let err = CelestrakProvider::from_csv(&b"MJD,\xff\n"[..]).unwrap_err();
+        assert!(matches!(err, Error::Read(ref e) if e.kind() == std::io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn csv_write_error() {
+        struct Full;
+
+        impl std::io::Write for Full {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let provider = CelestrakProvider::from_rows(&[(57000.0, 35, 0.0)]);
+        assert!(matches!(provider.to_csv(Full), Err(Error::Write(_))));
+    }
+
+    #[test]
+    fn builder() {
+        let provider = CelestrakProvider::builder()
+            .push(Entry {
+                x: Arcsec(0.2),
+                data_type: Type::Predicted,
+                ..Entry::new(Epoch::from_mjd(57001.0), 36, 0.5)
+            })
+            .entry(Epoch::from_mjd(57000.0), 35, -0.4)
+            .build();
+
+        // sorted by time, with complete entries kept as given
+        let entries = provider.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].time_utc, Epoch::from_mjd(57000.0));
+        assert!(entries[0].is_observed());
+        assert_eq!((entries[1].tai_utc, entries[1].x), (36, Arcsec(0.2)));
+        assert!(!entries[1].is_observed());
+
+        let empty = CelestrakProvider::builder().build();
+        assert!(empty.entries().is_empty());
+    }
+
+    #[test]
+    fn from_rows() {
+        let provider = CelestrakProvider::from_rows(&[(57001.0, 36, -0.5), (57000.0, 35, -0.4)]);
+        let entries = provider.entries();
+        assert_eq!(entries.len(), 2);
+
+        // sorted by time, with every other field zero
+        let entry = &entries[0];
+        assert_eq!(entry.time_utc, Epoch::from_mjd(57000.0));
+        assert_eq!((entry.tai_utc, entry.ut1_utc), (35, -0.4));
+        assert_eq!(
+            (entry.x, entry.y, entry.dpsi),
+            (Arcsec(0.0), Arcsec(0.0), Arcsec(0.0))
+        );
+        assert_eq!(entry.lod, 0.0);
+        assert!(entry.is_observed());
+        assert_eq!(entries[1].tai_utc, 36);
+    }
+
+    #[test]
+    fn ut1_utc_uncertainty() {
+        let at = |mjd| Epoch::<UTC>::from_mjd(mjd);
+
+        // straight lines interpolate exactly
+        let linear = CelestrakProvider::from_rows(&[
+            (57000.0, 35, 0.1),
+            (57001.0, 35, 0.2),
+            (57002.0, 35, 0.3),
+        ]);
+        let (value, sigma) = linear.ut1_utc_with_uncertainty(&at(57001.5)).unwrap();
+        assert!((value.to_seconds() - 0.25).abs() < 1e-9);
+        assert!(sigma.abs() < 1e-12);
+
+        // 0.001 s per day squared, so a quarter of that mid-gap
+        let curved = CelestrakProvider::from_rows(&[
+            (57000.0, 35, 0.000),
+            (57001.0, 35, 0.001),
+            (57002.0, 35, 0.004),
+            (57003.0, 35, 0.009),
+        ]);
+        let (_, sigma) = curved.ut1_utc_with_uncertainty(&at(57001.5)).unwrap();
+        assert!((sigma - 0.000_25).abs() < 1e-9);
+        let (_, sigma) = curved.ut1_utc_with_uncertainty(&at(57001.0)).unwrap();
+        assert_eq!(sigma, 0.0);
+
+        // not enough samples for curvature
+        let short = CelestrakProvider::from_rows(&[(57000.0, 35, 0.0), (57001.0, 35, 0.5)]);
+        let (_, sigma) = short.ut1_utc_with_uncertainty(&at(57000.5)).unwrap();
+        assert_eq!(sigma, 0.0);
+        assert_eq!(short.ut1_utc_with_uncertainty(&at(57002.0)), None);
+    }
+
+    #[test]
+    fn lookup_within_tolerance() {
+        // a five day gap after the second entry
+        let provider = CelestrakProvider::from_rows(&[
+            (57000.0, 35, 0.0),
+            (57001.0, 35, 0.0),
+            (57006.0, 35, 0.0),
+        ]);
+        let day = TimeDelta::new(86_400, 0).unwrap();
+
+        let t = Epoch::from_mjd(57000.5);
+        assert!(provider.get_utc_within(&t, day).is_some());
+        let t = Epoch::from_mjd(57001.0);
+        assert!(provider.get_utc_within(&t, day).is_none());
+        let t = Epoch::from_mjd(57003.5);
+        assert!(provider.get_utc(&t).is_some());
+        assert!(provider.get_utc_within(&t, day).is_none());
+        assert!(provider
+            .get_utc_within(&t, TimeDelta::new(3 * 86_400, 0).unwrap())
+            .is_some());
+        assert!(provider
+            .get_utc_within(&Epoch::from_mjd(57007.0), day)
+            .is_none());
+    }
 }
@@ -0,0 +1,192 @@
+//! Provider that combines two other providers.
+
+use alloc::vec::Vec;
+
+use super::{EopState, Provider};
+use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+/// A [Provider] that tries one provider, and falls back to another.
+///
+/// Every method is answered by `first` if it can, and by `second`
+/// otherwise. Each method falls through independently, so for
+/// example `first` can supply leap seconds while `second` supplies
+/// UT1 - UTC.
+///
+/// Chains of more than two providers can be built by nesting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChainProvider<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> ChainProvider<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B> Provider for ChainProvider<A, B>
+where
+    A: Provider,
+    B: Provider,
+{
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        self.first
+            .tai_utc_for_utc(epoch)
+            .or_else(|| self.second.tai_utc_for_utc(epoch))
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        self.first
+            .tai_utc_for_tai(epoch)
+            .or_else(|| self.second.tai_utc_for_tai(epoch))
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        self.first
+            .ut1_utc_for_utc(epoch)
+            .or_else(|| self.second.ut1_utc_for_utc(epoch))
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        self.first
+            .ut1_utc_for_ut1(epoch)
+            .or_else(|| self.second.ut1_utc_for_ut1(epoch))
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.first
+            .polar_motion_for_utc(epoch)
+            .or_else(|| self.second.polar_motion_for_utc(epoch))
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        self.first
+            .lod_for_utc(epoch)
+            .or_else(|| self.second.lod_for_utc(epoch))
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.first
+            .nutation_corrections_for_utc(epoch)
+            .or_else(|| self.second.nutation_corrections_for_utc(epoch))
+    }
+
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        self.first
+            .gps_utc_for_utc(epoch)
+            .or_else(|| self.second.gps_utc_for_utc(epoch))
+    }
+
+    // second only fills in changes that first does not know about
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        let mut leaps = self.first.leap_seconds();
+        for leap in self.second.leap_seconds() {
+            if let Err(idx) = leaps.binary_search_by_key(&leap.0, |(t, _)| *t) {
+                leaps.insert(idx, leap);
+            }
+        }
+        leaps
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        self.first
+            .full_state(epoch)
+            .or_else(|| self.second.full_state(epoch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::EmptyProvider;
+
+    // constant values, or none at all
+    struct Fixed {
+        tai_utc: Option<i64>,
+        ut1_utc: Option<f64>,
+        leaps: &'static [(f64, i64)],
+    }
+
+    impl Provider for Fixed {
+        fn tai_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+            TimeDelta::new(self.tai_utc?, 0)
+        }
+
+        fn tai_utc_for_tai(&self, _epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+            TimeDelta::new(self.tai_utc?, 0)
+        }
+
+        fn ut1_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+            Some(TimeDelta::from_seconds(self.ut1_utc?))
+        }
+
+        fn ut1_utc_for_ut1(&self, _epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+            Some(TimeDelta::from_seconds(self.ut1_utc?))
+        }
+
+        fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+            self.leaps
+                .iter()
+                .map(|&(mjd, tai_utc)| (Epoch::from_mjd(mjd), TimeDelta::new(tai_utc, 0).unwrap()))
+                .collect()
+        }
+    }
+
+    const TAI_ONLY: Fixed = Fixed {
+        tai_utc: Some(37),
+        ut1_utc: None,
+        leaps: &[(57_204.0, 36), (57_754.0, 37)],
+    };
+
+    const UT1_ONLY: Fixed = Fixed {
+        tai_utc: None,
+        ut1_utc: Some(0.25),
+        leaps: &[(56_109.0, 35), (57_204.0, 99)],
+    };
+
+    #[test]
+    fn falls_through_per_method() {
+        let t = Epoch::<UTC>::from_mjd(58_000.0);
+        for chain in [
+            ChainProvider::new(TAI_ONLY, UT1_ONLY),
+            ChainProvider::new(UT1_ONLY, TAI_ONLY),
+        ] {
+            assert_eq!(chain.tai_utc_for_utc(&t), TimeDelta::new(37, 0));
+            assert_eq!(chain.gps_utc_for_utc(&t), Some(18));
+            assert_eq!(chain.ut1_utc_for_utc(&t), TimeDelta::new(0, 250_000_000));
+        }
+
+        let chain = ChainProvider::new(EmptyProvider, EmptyProvider);
+        assert_eq!(chain.tai_utc_for_utc(&t), None);
+    }
+
+    #[test]
+    fn first_takes_priority() {
+        let other = Fixed {
+            tai_utc: Some(10),
+            ut1_utc: Some(-0.5),
+            leaps: &[],
+        };
+        let t = Epoch::<UTC>::from_mjd(58_000.0);
+        let chain = ChainProvider::new(TAI_ONLY, other);
+        assert_eq!(chain.tai_utc_for_utc(&t), TimeDelta::new(37, 0));
+        assert_eq!(
+            chain.ut1_utc_for_utc(&t),
+            Some(TimeDelta::from_seconds(-0.5))
+        );
+    }
+
+    #[test]
+    fn merges_leap_seconds() {
+        let leaps: Vec<_> = ChainProvider::new(TAI_ONLY, UT1_ONLY)
+            .leap_seconds()
+            .into_iter()
+            .map(|(t, tai_utc)| (t.mjd(), tai_utc.to_raw().0))
+            .collect();
+
+        // first wins where both know about a change
+        assert_eq!(leaps, [(56_109.0, 35), (57_204.0, 36), (57_754.0, 37)]);
+    }
+}
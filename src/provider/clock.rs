@@ -0,0 +1,135 @@
+//! Linear model for an onboard clock.
+
+use crate::time::{CustomScale, Epoch, Scale, TimeDelta, TAI};
+
+use super::Provider;
+
+/// A model of an onboard clock, such as a spacecraft clock, running
+/// at a constant rate relative to TAI.
+///
+/// > clock - TAI = offset + rate * (TAI - epoch)
+///
+/// The offset is in seconds at `epoch`, and the rate is in seconds
+/// per second, so a clock that gains 1 ms per day has a rate of
+/// 0.001 / 86400. These are usually fit from telemetry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearClock {
+    epoch: Epoch<TAI>,
+    offset: TimeDelta<TAI>,
+    rate: f64,
+}
+
+impl LinearClock {
+    /// Model a clock that is `offset` ahead of TAI at `epoch`, and
+    /// gains `rate` seconds per second after that.
+    pub fn new(epoch: Epoch<TAI>, offset: TimeDelta<TAI>, rate: f64) -> Self {
+        Self {
+            epoch,
+            offset,
+            rate,
+        }
+    }
+
+    /// Return clock - TAI for the given TAI [Epoch].
+    pub fn offset_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        let elapsed = (*epoch - self.epoch).to_seconds();
+        Some(self.offset + TimeDelta::try_from_seconds(self.rate * elapsed)?)
+    }
+
+    /// Return clock - TAI for the given [Epoch] read from the clock.
+    pub fn offset_for_clock<S>(&self, epoch: &Epoch<S>) -> Option<TimeDelta<TAI>> {
+        // clock - epoch = (TAI - epoch) * (1 + rate) + offset
+        let elapsed = (epoch.transmute() - self.epoch - self.offset).to_seconds();
+        let drift = self.rate * elapsed / (1.0 + self.rate);
+        Some(self.offset + TimeDelta::try_from_seconds(drift)?)
+    }
+}
+
+/// A [CustomScale] for a clock described by a [LinearClock].
+///
+/// Implement this for your clock's [Scale], returning its model, and
+/// [CustomScale] is implemented for it. The model does not come from
+/// the Earth orientation [Provider], which is only consulted for
+/// conversions beyond [TAI], such as to [UTC][crate::time::UTC].
+pub trait LinearClockProvider: Scale {
+    /// The model for this clock.
+    fn clock() -> LinearClock;
+}
+
+impl<S> CustomScale for S
+where
+    S: LinearClockProvider,
+{
+    fn to_tai_with<P>(epoch: &Epoch<Self>, _provider: &P) -> Option<Epoch<TAI>>
+    where
+        P: Provider,
+    {
+        Some(epoch.transmute() - S::clock().offset_for_clock(epoch)?)
+    }
+
+    fn from_tai_with<P>(epoch: &Epoch<TAI>, _provider: &P) -> Option<Epoch<Self>>
+    where
+        P: Provider,
+    {
+        Some((*epoch + S::clock().offset_for_tai(epoch)?).transmute())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::EmptyProvider;
+    use crate::time::epoch::J2000;
+    use crate::time::TT;
+
+    // 10 s ahead of TAI at J2000, gaining 1 ms per day
+    struct Onboard;
+
+    impl Scale for Onboard {
+        const NAME: &'static str = "ONBOARD";
+    }
+
+    impl LinearClockProvider for Onboard {
+        fn clock() -> LinearClock {
+            let offset = TimeDelta::new(10, 0).unwrap();
+            LinearClock::new(J2000.to_tai(), offset, 0.001 / 86_400.0)
+        }
+    }
+
+    #[test]
+    fn linear_drift() {
+        let clock = Onboard::clock();
+        let start = J2000.to_tai();
+        let offset = |tai| clock.offset_for_tai(&tai).unwrap().to_seconds();
+
+        assert_eq!(offset(start), 10.0);
+        let day = TimeDelta::new(86_400, 0).unwrap();
+        assert!((offset(start + day) - 10.001).abs() < 1e-9);
+        assert!((offset(start - day) - 9.999).abs() < 1e-9);
+    }
+
+    #[test]
+    fn drift_after_ten_days() {
+        let tai = J2000.to_tai() + TimeDelta::from_days(10.0);
+        let onboard: Epoch<Onboard> = tai.convert(&EmptyProvider).unwrap();
+
+        // 10 s to start with, and 1 ms for each day
+        let offset = (onboard.transmute::<TAI>() - tai).to_seconds();
+        assert!((offset - 10.010).abs() < 1e-9);
+    }
+
+    #[test]
+    fn clock_round_trip() {
+        for days in [-1_000.0, -0.5, 0.0, 0.25, 365.0] {
+            let tai = J2000.to_tai() + TimeDelta::from_days(days);
+            let onboard = tai.convert::<Onboard, _>(&EmptyProvider).unwrap();
+            let back = onboard.convert::<TAI, _>(&EmptyProvider).unwrap();
+            assert!((back - tai).to_seconds().abs() < 1e-9, "{days}");
+        }
+
+        // and through TAI to the other scales
+        let onboard: Epoch<Onboard> = J2000.convert(&EmptyProvider).unwrap();
+        let tt = onboard.convert::<TT, _>(&EmptyProvider).unwrap();
+        assert!((tt - J2000).to_seconds().abs() < 1e-9);
+    }
+}
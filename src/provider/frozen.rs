@@ -0,0 +1,265 @@
+//! Frozen orientation provider.
+//!
+//! A [FrozenProvider] holds the same data as a [CelestrakProvider],
+//! but laid out as separate sorted arrays and searched with a binary
+//! search. It cannot be modified, and is meant for read-heavy
+//! workloads after all data has been loaded.
+//!
+//! Use [CelestrakProvider::into_frozen] to create one.
+
+use super::celestrak::{lerp_known, Entry};
+use super::{CelestrakProvider, EopState, LeapSecondsIter};
+use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+#[derive(Debug, Clone)]
+pub struct FrozenProvider {
+    time_utc: Vec<Epoch<UTC>>,
+    time_tai: Vec<Epoch<TAI>>,
+    time_ut1: Vec<Epoch<UT1>>,
+    tai_utc: Vec<i64>,
+    ut1_utc: Vec<f64>,
+    x: Vec<Arcsec>,
+    y: Vec<Arcsec>,
+    lod: Vec<f64>,
+    dpsi: Vec<Arcsec>,
+    deps: Vec<Arcsec>,
+    dx: Vec<Arcsec>,
+    dy: Vec<Arcsec>,
+}
+
+// find the samples on either side of t, and the interpolation weight
+// of the later sample
+fn locate<S>(times: &[Epoch<S>], t: &Epoch<S>) -> Option<(usize, f64)> {
+    let idx = times.partition_point(|e| e <= t);
+
+    if idx == 0 || idx == times.len() {
+        return None;
+    }
+
+    let g = (*t - times[idx - 1]).to_seconds() / (times[idx] - times[idx - 1]).to_seconds();
+    Some((idx, g))
+}
+
+fn lerp(values: &[f64], idx: usize, g: f64) -> f64 {
+    lerp_known(values[idx - 1], values[idx], g)
+}
+
+fn lerp_arcsec(values: &[Arcsec], idx: usize, g: f64) -> Arcsec {
+    Arcsec(lerp_known(values[idx - 1].0, values[idx].0, g))
+}
+
+impl FrozenProvider {
+    pub(super) fn from_sorted_entries(entries: &[Entry]) -> Self {
+        Self {
+            time_utc: entries.iter().map(|e| e.time_utc).collect(),
+            time_tai: entries.iter().map(|e| e.time_tai()).collect(),
+            time_ut1: entries.iter().map(|e| e.time_ut1()).collect(),
+            tai_utc: entries.iter().map(|e| e.tai_utc).collect(),
+            ut1_utc: entries.iter().map(|e| e.ut1_utc).collect(),
+            x: entries.iter().map(|e| e.x).collect(),
+            y: entries.iter().map(|e| e.y).collect(),
+            lod: entries.iter().map(|e| e.lod).collect(),
+            dpsi: entries.iter().map(|e| e.dpsi).collect(),
+            deps: entries.iter().map(|e| e.deps).collect(),
+            dx: entries.iter().map(|e| e.dx).collect(),
+            dy: entries.iter().map(|e| e.dy).collect(),
+        }
+    }
+
+    // interpolate UT1 - TAI, which is smooth across leap seconds, and
+    // add back the TAI - UTC held from the earlier sample
+    fn ut1_utc(&self, idx: usize, g: f64) -> f64 {
+        let tai_utc = |i: usize| self.tai_utc[i] as f64;
+        let ut1_tai = lerp_known(
+            self.ut1_utc[idx - 1] - tai_utc(idx - 1),
+            self.ut1_utc[idx] - tai_utc(idx),
+            g,
+        );
+        ut1_tai + tai_utc(idx - 1)
+    }
+
+    /// The number of samples in this provider.
+    pub fn len(&self) -> usize {
+        self.time_utc.len()
+    }
+
+    /// Returns `true` if this provider has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.time_utc.is_empty()
+    }
+}
+
+impl std::convert::From<CelestrakProvider> for FrozenProvider {
+    fn from(value: CelestrakProvider) -> Self {
+        value.into_frozen()
+    }
+}
+
+impl super::Provider for FrozenProvider {
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        let (idx, _) = locate(&self.time_utc, epoch)?;
+        TimeDelta::new(self.tai_utc[idx - 1], 0)
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        let (idx, _) = locate(&self.time_tai, epoch)?;
+        TimeDelta::new(self.tai_utc[idx - 1], 0)
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        let (idx, g) = locate(&self.time_utc, epoch)?;
+        Some(TimeDelta::from_seconds(self.ut1_utc(idx, g)))
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        let (idx, g) = locate(&self.time_ut1, epoch)?;
+        Some(TimeDelta::from_seconds(self.ut1_utc(idx, g)))
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        let (idx, g) = locate(&self.time_utc, epoch)?;
+        Some((lerp_arcsec(&self.x, idx, g), lerp_arcsec(&self.y, idx, g)))
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        let (idx, g) = locate(&self.time_utc, epoch)?;
+        Some(lerp(&self.lod, idx, g)).filter(|lod| !lod.is_nan())
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        let (idx, g) = locate(&self.time_utc, epoch)?;
+        Some((
+            lerp_arcsec(&self.dpsi, idx, g),
+            lerp_arcsec(&self.deps, idx, g),
+        ))
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.leap_seconds_iter().collect()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        Box::new(
+            (1..self.len())
+                .filter(|&idx| self.tai_utc[idx - 1] != self.tai_utc[idx])
+                .filter_map(|idx| {
+                    Some((self.time_utc[idx], TimeDelta::new(self.tai_utc[idx], 0)?))
+                }),
+        )
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        let (idx, g) = locate(&self.time_utc, epoch)?;
+        Some(EopState {
+            x: lerp_arcsec(&self.x, idx, g),
+            y: lerp_arcsec(&self.y, idx, g),
+            ut1_utc: self.ut1_utc(idx, g),
+            lod: lerp(&self.lod, idx, g),
+            dpsi: lerp_arcsec(&self.dpsi, idx, g),
+            deps: lerp_arcsec(&self.deps, idx, g),
+            dx: lerp_arcsec(&self.dx, idx, g),
+            dy: lerp_arcsec(&self.dy, idx, g),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+
+    // a couple hundred days of wobbly data, with a leap second
+    fn provider() -> CelestrakProvider {
+        let entries = (0..200)
+            .map(|day| {
+                let t = day as f64;
+                let tai_utc = if day < 120 { 36 } else { 37 };
+                Entry {
+                    x: Arcsec(0.1 * libm::sin(t / 30.0)),
+                    y: Arcsec(0.3 * libm::cos(t / 30.0)),
+                    lod: 0.001 + 0.0001 * libm::sin(t / 7.0),
+                    dpsi: Arcsec(-0.05 + 0.001 * t),
+                    ..Entry::new(
+                        Epoch::from_mjd(57_634.0 + t),
+                        tai_utc,
+                        0.4 - 0.002 * t + (tai_utc - 36) as f64,
+                    )
+                }
+            })
+            .collect();
+        CelestrakProvider::from_entries(entries)
+    }
+
+    #[test]
+    fn frozen_matches_celestrak() {
+        let celestrak = provider();
+        let frozen = celestrak.clone().into_frozen();
+        assert_eq!(frozen.len(), 200);
+        assert_eq!(frozen.leap_seconds(), celestrak.leap_seconds());
+
+        for i in -10..2_010 {
+            let utc = Epoch::<UTC>::from_mjd(57_634.0 + i as f64 * 0.1);
+            let tai = Epoch::<TAI>::from_mjd(57_634.0 + i as f64 * 0.1);
+            let ut1 = Epoch::<UT1>::from_mjd(57_634.0 + i as f64 * 0.1);
+
+            assert_eq!(
+                frozen.tai_utc_for_utc(&utc),
+                celestrak.tai_utc_for_utc(&utc)
+            );
+            assert_eq!(
+                frozen.tai_utc_for_tai(&tai),
+                celestrak.tai_utc_for_tai(&tai)
+            );
+            assert_eq!(
+                frozen.ut1_utc_for_utc(&utc),
+                celestrak.ut1_utc_for_utc(&utc)
+            );
+            assert_eq!(
+                frozen.ut1_utc_for_ut1(&ut1),
+                celestrak.ut1_utc_for_ut1(&ut1)
+            );
+            assert_eq!(
+                frozen.polar_motion_for_utc(&utc),
+                celestrak.polar_motion_for_utc(&utc)
+            );
+            assert_eq!(frozen.lod_for_utc(&utc), celestrak.lod_for_utc(&utc));
+            assert_eq!(frozen.full_state(&utc), celestrak.full_state(&utc));
+        }
+    }
+
+    #[test]
+    fn frozen_across_leap_second() {
+        let frozen = provider().into_frozen();
+
+        // halfway through the day before the leap second, UT1 - UTC
+        // follows UT1 - TAI rather than jumping towards the next sample
+        let utc = Epoch::<UTC>::from_mjd(57_753.5);
+        let ut1_utc = frozen.ut1_utc_for_utc(&utc).unwrap().to_seconds();
+        assert!((ut1_utc - 0.161).abs() < 1e-9);
+        let state = frozen.full_state(&utc).unwrap();
+        assert!((state.ut1_utc - 0.161).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frozen_leap_seconds_iter() {
+        let celestrak = provider();
+        let frozen = celestrak.clone().into_frozen();
+
+        let leap = (Epoch::from_mjd(57_754.0), TimeDelta::new(37, 0).unwrap());
+        assert!(frozen.leap_seconds_iter().eq(celestrak.leap_seconds_iter()));
+        assert_eq!(frozen.leap_seconds(), [leap]);
+
+        // double ended, so the latest change is cheap to find
+        assert_eq!(frozen.leap_seconds_iter().next_back(), Some(leap));
+        assert_eq!(celestrak.leap_seconds_iter().next_back(), Some(leap));
+    }
+
+    #[test]
+    fn frozen_empty() {
+        let frozen = CelestrakProvider::from_entries(Vec::new()).into_frozen();
+        assert!(frozen.is_empty());
+        assert_eq!(frozen.tai_utc_for_utc(&Epoch::from_mjd(57_634.0)), None);
+        assert_eq!(frozen.leap_seconds_iter().count(), 0);
+    }
+}
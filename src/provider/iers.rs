@@ -0,0 +1,317 @@
+//! IERS Bulletin A orientation data.
+//!
+//! This module reads the fixed-column [`finals2000A` files][finals]
+//! published by the IERS Rapid Service, such as `finals2000A.all` or
+//! `finals2000A.daily`. These combine Bulletin A observations and
+//! predictions with Bulletin B values where available.
+//!
+//! [finals]: https://maia.usno.navy.mil/ser7/readme.finals2000A
+//!
+//! The Bulletin A columns are used. These files do not contain TAI -
+//! UTC, so a second [Provider] is needed to supply leap second
+//! info. They also do not contain the nutation corrections `dpsi`
+//! and `deps`, which are filled with NaN. Predicted rows often leave
+//! LOD, `dX` and `dY` blank, and these are also filled with NaN.
+//! Rows at the end of the file with no UT1 - UTC are skipped.
+
+use std::io::BufRead;
+
+use super::celestrak::{Entry, Type};
+use super::{CelestrakProvider, EopState, LeapSecondsIter, Provider};
+use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("error reading finals data")]
+    Read(#[from] std::io::Error),
+    #[error("line {0} of the finals file is missing column {1}")]
+    MissingField(usize, &'static str),
+    #[error("column {1} of line {0} of the finals file failed to parse")]
+    BadParse(usize, &'static str),
+    #[error("no TAI - UTC is available for line {0} of the finals file")]
+    MissingTaiUtc(usize),
+}
+
+/// An orientation provider backed by IERS `finals2000A` data.
+///
+/// Values are interpolated the same way as in [CelestrakProvider].
+#[derive(Debug, Clone)]
+pub struct IersProvider {
+    inner: CelestrakProvider,
+}
+
+// the text in a fixed column range, or None if it is blank
+fn field(line: &str, range: std::ops::Range<usize>) -> Option<&str> {
+    let end = range.end.min(line.len());
+    line.get(range.start.min(end)..end)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+// column ranges are 0-based, end exclusive
+macro_rules! get_column {
+    ($linei:expr, $line:expr, $range:expr, $name:expr) => {
+        field($line, $range)
+            .ok_or_else(|| Error::MissingField($linei, $name))
+            .and_then(|s| s.parse::<f64>().map_err(|_| Error::BadParse($linei, $name)))
+    };
+}
+
+// like get_column!, but blank columns are NaN
+macro_rules! get_column_or_nan {
+    ($linei:expr, $line:expr, $range:expr, $name:expr) => {
+        match field($line, $range) {
+            Some(s) => s.parse::<f64>().map_err(|_| Error::BadParse($linei, $name)),
+            None => Ok(f64::NAN),
+        }
+    };
+}
+
+/// Read all [Entry]s from a `finals2000A` file.
+///
+/// TAI - UTC for each entry is taken from `leap_seconds`. Line
+/// numbers in errors start at 1.
+pub fn read_entries<R, P>(file: R, leap_seconds: &P) -> Result<Vec<Entry>, Error>
+where
+    R: std::io::Read,
+    P: Provider,
+{
+    let mut entries = Vec::new();
+    for (linei, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let linei = linei + 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // the far end of the file has dates, but no data yet
+        if field(&line, 58..68).is_none() {
+            continue;
+        }
+
+        let time_utc = Epoch::from_modified_julian_day(TimeDelta::from_days(get_column!(
+            linei,
+            &line,
+            7..15,
+            "MJD"
+        )?));
+
+        // pre-1972 TAI - UTC is not integral, but this is the best we can do
+        let tai_utc = leap_seconds
+            .tai_utc_for_utc(&time_utc)
+            .ok_or(Error::MissingTaiUtc(linei))?
+            .to_raw()
+            .0;
+
+        let data_type = match field(&line, 57..58) {
+            Some("I") => Type::Observed,
+            Some("P") => Type::Predicted,
+            _ => return Err(Error::BadParse(linei, "UT1-UTC flag")),
+        };
+
+        // LOD is in milliseconds, dX and dY in milli-arc-seconds
+        entries.push(Entry {
+            time_utc,
+            x: Arcsec(get_column!(linei, &line, 18..27, "PM-x")?),
+            y: Arcsec(get_column!(linei, &line, 37..46, "PM-y")?),
+            ut1_utc: get_column!(linei, &line, 58..68, "UT1-UTC")?,
+            lod: get_column_or_nan!(linei, &line, 79..86, "LOD")? / 1000.0,
+            dpsi: Arcsec::NAN,
+            deps: Arcsec::NAN,
+            dx: Arcsec::from_milliarcsec(get_column_or_nan!(linei, &line, 97..106, "dX")?),
+            dy: Arcsec::from_milliarcsec(get_column_or_nan!(linei, &line, 116..125, "dY")?),
+            tai_utc,
+            data_type,
+        });
+    }
+
+    Ok(entries)
+}
+
+impl IersProvider {
+    /// Load a provider from a `finals2000A` file, taking TAI - UTC
+    /// from `leap_seconds`.
+    ///
+    /// See [read_entries].
+    pub fn from_finals<R, P>(file: R, leap_seconds: &P) -> Result<Self, Error>
+    where
+        R: std::io::Read,
+        P: Provider,
+    {
+        Ok(Self {
+            inner: CelestrakProvider::from_entries(read_entries(file, leap_seconds)?),
+        })
+    }
+
+    /// The underlying interpolated data.
+    pub fn as_celestrak(&self) -> &CelestrakProvider {
+        &self.inner
+    }
+
+    /// Convert into a [CelestrakProvider] holding the same data.
+    pub fn into_celestrak(self) -> CelestrakProvider {
+        self.inner
+    }
+}
+
+impl Provider for IersProvider {
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        self.inner.tai_utc_for_utc(epoch)
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        self.inner.tai_utc_for_tai(epoch)
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        self.inner.ut1_utc_for_utc(epoch)
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        self.inner.ut1_utc_for_ut1(epoch)
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner.polar_motion_for_utc(epoch)
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        self.inner.lod_for_utc(epoch)
+    }
+
+    // finals2000A has no dpsi or deps at all
+    fn nutation_corrections_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        None
+    }
+
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        self.inner.gps_utc_for_utc(epoch)
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.inner.leap_seconds()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        self.inner.leap_seconds_iter()
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        self.inner.full_state(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // write s right-aligned into a fixed column range
+    fn put(line: &mut [u8], range: std::ops::Range<usize>, s: &str) {
+        let s = format!("{s:>width$}", width = range.len());
+        line[range].copy_from_slice(s.as_bytes());
+    }
+
+    // a finals2000A line, with only the columns read here filled in
+    fn finals_line(mjd: f64, flag: &str, ut1_utc: Option<f64>, lod: Option<f64>) -> String {
+        let mut line = vec![b' '; 125];
+        put(&mut line, 7..15, &format!("{mjd:.2}"));
+        put(&mut line, 18..27, "0.120733");
+        put(&mut line, 37..46, "0.136966");
+        if let Some(ut1_utc) = ut1_utc {
+            put(&mut line, 57..58, flag);
+            put(&mut line, 58..68, &format!("{ut1_utc:.7}"));
+        }
+        if let Some(lod) = lod {
+            put(&mut line, 79..86, &format!("{lod:.4}"));
+        }
+        put(&mut line, 97..106, "-0.766");
+        put(&mut line, 116..125, "0.199");
+        String::from_utf8(line).unwrap()
+    }
+
+    // the first rows of finals2000A.all, and rows from the end of the
+    // predictions, where the data runs out
+    const FINALS: &str = "\
+73 1 2 41684.00 I  0.120733 0.009786  0.136966 0.015902  I 0.8084178 0.0002710  0.0000 0.1916  P    -0.766    0.199    -0.720    0.300   .143000   .137000   .8075000     -.766     -.720
+73 1 3 41685.00 I  0.118980 0.011039  0.135656 0.013616  I 0.8056163 0.0002710  3.5563 0.1916  P    -0.751    0.199    -0.701    0.300   .141000   .134000   .8044000     -.751     -.701
+26 4 9 61139.00 P  0.101546 0.004890  0.412843 0.006838  P 0.0443622 0.0052370                 P     0.255    0.128     0.005    0.160
+26 410 61140.00 P  0.100652 0.004939  0.411874 0.006905  P 0.0436138 0.0052914                 P     0.255    0.128     0.004    0.160
+26 411 61141.00
+26 412 61142.00
+";
+
+    fn leap_seconds() -> CelestrakProvider {
+        CelestrakProvider::from_rows(&[
+            (41_683.0, 12, 0.0),
+            (41_700.0, 12, 0.0),
+            (56_999.0, 35, 0.0),
+            (57_010.0, 35, 0.0),
+            (61_100.0, 37, 0.0),
+            (61_200.0, 37, 0.0),
+        ])
+    }
+
+    #[test]
+    fn read_finals() {
+        let provider = IersProvider::from_finals(FINALS.as_bytes(), &leap_seconds()).unwrap();
+        let entries = provider.as_celestrak().entries();
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(entries[0].time_utc, Epoch::from_mjd(41684.0));
+        assert_eq!(entries[0].tai_utc, 12);
+        assert_eq!(entries[0].x, Arcsec(0.120733));
+        assert_eq!(entries[0].y, Arcsec(0.136966));
+        assert_eq!(entries[0].ut1_utc, 0.8084178);
+        assert_eq!(entries[0].lod, 0.0);
+        assert!((entries[0].dx.0 + 0.000766).abs() < 1e-12);
+        assert!((entries[0].dy.0 + 0.000720).abs() < 1e-12);
+        assert!(entries[0].dpsi.is_nan());
+        assert_eq!(entries[0].data_type, Type::Observed);
+        assert!((entries[1].lod - 0.0035563).abs() < 1e-12);
+
+        // predictions have no LOD
+        assert_eq!(entries[2].time_utc, Epoch::from_mjd(61139.0));
+        assert_eq!(entries[2].tai_utc, 37);
+        assert_eq!(entries[2].ut1_utc, 0.0443622);
+        assert!(entries[2].lod.is_nan());
+        assert!((entries[2].dx.0 - 0.000255).abs() < 1e-12);
+        assert_eq!(entries[2].data_type, Type::Predicted);
+        assert_eq!(entries[3].data_type, Type::Predicted);
+
+        let t = Epoch::<UTC>::from_mjd(41684.5);
+        assert_eq!(provider.tai_utc_for_utc(&t), TimeDelta::new(12, 0));
+        assert_eq!(provider.nutation_corrections_for_utc(&t), None);
+        let t = Epoch::<UTC>::from_mjd(61141.0);
+        assert_eq!(provider.ut1_utc_for_utc(&t), None);
+    }
+
+    #[test]
+    fn read_finals_errors() {
+        let line = finals_line(57000.0, "I", Some(-0.4), None);
+        assert!(matches!(
+            read_entries(line.as_bytes(), &crate::provider::EmptyProvider),
+            Err(Error::MissingTaiUtc(1))
+        ));
+
+        let line = finals_line(57000.0, "X", Some(-0.4), None);
+        assert!(matches!(
+            read_entries(line.as_bytes(), &leap_seconds()),
+            Err(Error::BadParse(1, "UT1-UTC flag"))
+        ));
+
+        let text = format!("\n{}", line.replace("57000.00", "570xx.00"));
+        assert!(matches!(
+            read_entries(text.as_bytes(), &leap_seconds()),
+            Err(Error::BadParse(2, "MJD"))
+        ));
+
+        let mut line = finals_line(57000.0, "I", Some(-0.4), None).into_bytes();
+        put(&mut line, 18..27, "");
+        assert!(matches!(
+            read_entries(&line[..], &leap_seconds()),
+            Err(Error::MissingField(1, "PM-x"))
+        ));
+    }
+}
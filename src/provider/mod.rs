@@ -1,10 +1,45 @@
 //! Providers for Earth orientation data and leap seconds.
 
-use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
+use crate::time::{Epoch, TimeDelta, ToScaleWith, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+#[cfg(feature = "std")]
+pub mod binary;
+#[cfg(feature = "std")]
+pub mod c04;
+#[cfg(feature = "std")]
+pub mod caching;
+#[cfg(feature = "std")]
 pub mod celestrak;
+pub mod chain;
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod frozen;
+#[cfg(feature = "std")]
+pub mod iers;
+pub mod smearing;
+pub mod traced;
 
+#[cfg(feature = "std")]
+pub use caching::CachingProvider;
+#[cfg(feature = "std")]
 pub use celestrak::CelestrakProvider;
+pub use chain::ChainProvider;
+pub use clock::{LinearClock, LinearClockProvider};
+#[cfg(feature = "std")]
+pub use frozen::FrozenProvider;
+#[cfg(feature = "std")]
+pub use iers::IersProvider;
+pub use smearing::SmearingProvider;
+pub use traced::TracedProvider;
+
+/// An iterator over changes in TAI - UTC, from
+/// [Provider::leap_seconds_iter].
+pub type LeapSecondsIter<'a> =
+    Box<dyn DoubleEndedIterator<Item = (Epoch<UTC>, TimeDelta<TAI>)> + 'a>;
 
 /// An Earth orientation provider.
 ///
@@ -20,6 +55,10 @@ pub use celestrak::CelestrakProvider;
 ///
 /// Look at the documentation for implementors of this trait to learn
 /// how to load this data for use.
+///
+/// This trait is object safe, so a provider chosen at runtime can be
+/// stored as a `Box<dyn Provider>`. Boxes and references to providers
+/// are providers too. Generic conveniences live in [ProviderExt].
 pub trait Provider {
     /// Return TAI - UTC for the given UTC [Epoch].
     ///
@@ -40,14 +79,275 @@ pub trait Provider {
     ///
     /// Returns [None] if data for this epoch is not available.
     fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>>;
+
+    /// Return polar motion `(x, y)` in arc-seconds for the given UTC
+    /// [Epoch].
+    ///
+    /// Returns [None] if data for this epoch is not available. The
+    /// default implementation always returns [None].
+    fn polar_motion_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        None
+    }
+
+    /// Return nutation corrections `(dpsi, deps)` in arc-seconds for
+    /// the given UTC [Epoch].
+    ///
+    /// Returns [None] if data for this epoch is not available. The
+    /// default implementation always returns [None].
+    fn nutation_corrections_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        None
+    }
+
+    /// Return the excess length of day in seconds for the given UTC
+    /// [Epoch].
+    ///
+    /// This is how much longer than 86,400 s the day was, measured
+    /// in UT1. Returns [None] if data for this epoch is not
+    /// available. The default implementation always returns [None].
+    fn lod_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<f64> {
+        None
+    }
+
+    /// Return GPS - UTC in whole seconds for the given UTC [Epoch].
+    ///
+    /// This is the leap second offset reported by many GNSS
+    /// receivers, equal to TAI - UTC - 19 s. Returns [None] if data
+    /// for this epoch is not available.
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        let (tai_utc, _) = self.tai_utc_for_utc(epoch)?.to_raw();
+        Some(tai_utc - 19)
+    }
+
+    /// List every change in TAI - UTC known to this provider.
+    ///
+    /// Each item is the UTC [Epoch] where a change takes effect, and
+    /// TAI - UTC from then on. Items are sorted by time. The default
+    /// implementation returns an empty list.
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        Vec::new()
+    }
+
+    /// Iterate over every change in TAI - UTC known to this provider.
+    ///
+    /// This yields the same items as
+    /// [leap_seconds][Self::leap_seconds], but providers that can
+    /// will avoid building the whole list. The iterator is double
+    /// ended, so the most recent change is `next_back()`. The default
+    /// implementation iterates over
+    /// [leap_seconds][Self::leap_seconds].
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        Box::new(self.leap_seconds().into_iter())
+    }
+
+    /// Return the net change in TAI - UTC across `[a, b)`.
+    ///
+    /// This is the number of leap seconds inserted in that interval,
+    /// minus any removed, counting a change that takes effect exactly
+    /// at `a` but not one exactly at `b`. If `b` is before `a`, the
+    /// interval is `[b, a)` and the result is negated. Returns [None]
+    /// if data for either end is not available.
+    ///
+    /// The default implementation adds up the changes from
+    /// [leap_seconds_iter][Self::leap_seconds_iter], so a provider
+    /// that does not list its leap seconds reports no change.
+    fn leap_seconds_between(&self, a: &Epoch<UTC>, b: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        let (start, end) = if a <= b { (a, b) } else { (b, a) };
+
+        // the data must cover the whole interval
+        self.tai_utc_for_utc(start)?;
+        self.tai_utc_for_utc(end)?;
+
+        let mut net = TimeDelta::new(0, 0)?;
+        let mut previous = None;
+        for (at, tai_utc) in self.leap_seconds_iter() {
+            if at >= *end {
+                break;
+            }
+            if at >= *start {
+                // the first change in the list has nothing before it
+                let before = match previous {
+                    Some(before) => before,
+                    None => self.tai_utc_for_utc(&(at - TimeDelta::new(0, 1)?))?,
+                };
+                net += tai_utc - before;
+            }
+            previous = Some(tai_utc);
+        }
+
+        Some(if a <= b { net } else { -net })
+    }
+
+    /// Return all available Earth orientation parameters for the
+    /// given UTC [Epoch] at once.
+    ///
+    /// Parameters that this provider does not know are NaN. Returns
+    /// [None] if data for this epoch is not available.
+    ///
+    /// The default implementation assembles this from the other
+    /// methods on this trait.
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        let (x, y) = self
+            .polar_motion_for_utc(epoch)
+            .unwrap_or((Arcsec::NAN, Arcsec::NAN));
+        let (dpsi, deps) = self
+            .nutation_corrections_for_utc(epoch)
+            .unwrap_or((Arcsec::NAN, Arcsec::NAN));
+
+        Some(EopState {
+            x,
+            y,
+            ut1_utc: self.ut1_utc_for_utc(epoch)?.to_seconds(),
+            lod: self.lod_for_utc(epoch).unwrap_or(f64::NAN),
+            dpsi,
+            deps,
+            ..EopState::UNKNOWN
+        })
+    }
+}
+
+/// Extra methods for every [Provider].
+pub trait ProviderExt: Provider + Sized {
+    /// Return TAI - UTC for an [Epoch] in any [Scale][crate::time::Scale]
+    /// that can be converted to TAI.
+    ///
+    /// The epoch is first converted to TAI, and then passed to
+    /// [tai_utc_for_tai][Provider::tai_utc_for_tai]. For scales such
+    /// as [TT][crate::time::TT] and [GPS][crate::time::GPS], this
+    /// conversion does not use the provider. For [UTC], it uses
+    /// [tai_utc_for_utc][Provider::tai_utc_for_utc], which should
+    /// agree. Since this calls back into the [Provider], do not
+    /// implement [Provider] methods in terms of it.
+    ///
+    /// Returns [None] if the conversion fails, or if data for this
+    /// epoch is not available.
+    fn tai_utc_for<S>(&self, epoch: &Epoch<S>) -> Option<TimeDelta<TAI>>
+    where
+        Epoch<S>: ToScaleWith<TAI>,
+    {
+        self.tai_utc_for_tai(&epoch.to_scale_with(self)?)
+    }
+}
+
+impl<P> ProviderExt for P where P: Provider {}
+
+// keep Provider object safe
+const _: Option<&dyn Provider> = None;
+
+// forward every method through a pointer to a provider
+macro_rules! impl_provider_deref {
+    ($($Ptr:ty),*) => {
+        $(
+            impl<P> Provider for $Ptr
+            where
+                P: Provider + ?Sized,
+            {
+                fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+                    (**self).tai_utc_for_utc(epoch)
+                }
+
+                fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+                    (**self).tai_utc_for_tai(epoch)
+                }
+
+                fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+                    (**self).ut1_utc_for_utc(epoch)
+                }
+
+                fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+                    (**self).ut1_utc_for_ut1(epoch)
+                }
+
+                fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+                    (**self).polar_motion_for_utc(epoch)
+                }
+
+                fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+                    (**self).nutation_corrections_for_utc(epoch)
+                }
+
+                fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+                    (**self).lod_for_utc(epoch)
+                }
+
+                fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+                    (**self).gps_utc_for_utc(epoch)
+                }
+
+                fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+                    (**self).leap_seconds()
+                }
+
+                fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+                    (**self).leap_seconds_iter()
+                }
+
+                fn leap_seconds_between(
+                    &self,
+                    a: &Epoch<UTC>,
+                    b: &Epoch<UTC>,
+                ) -> Option<TimeDelta<TAI>> {
+                    (**self).leap_seconds_between(a, b)
+                }
+
+                fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+                    (**self).full_state(epoch)
+                }
+            }
+        )*
+    };
+}
+
+impl_provider_deref!(&P, Box<P>);
+
+/// A snapshot of Earth orientation parameters at a single moment.
+///
+/// Unknown values are NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EopState {
+    /// Polar motion x, arc-seconds.
+    pub x: Arcsec,
+
+    /// Polar motion y, arc-seconds.
+    pub y: Arcsec,
+
+    /// UT1 - UTC, seconds.
+    pub ut1_utc: f64,
+
+    /// Length of day, seconds.
+    pub lod: f64,
+
+    /// Nutation correction in longitude, arc-seconds.
+    pub dpsi: Arcsec,
+
+    /// Nutation correction in obliquity, arc-seconds.
+    pub deps: Arcsec,
+
+    /// Celestial pole offset dX, arc-seconds.
+    pub dx: Arcsec,
+
+    /// Celestial pole offset dY, arc-seconds.
+    pub dy: Arcsec,
+}
+
+impl EopState {
+    /// An [EopState] where every parameter is unknown.
+    pub const UNKNOWN: Self = Self {
+        x: Arcsec::NAN,
+        y: Arcsec::NAN,
+        ut1_utc: f64::NAN,
+        lod: f64::NAN,
+        dpsi: Arcsec::NAN,
+        deps: Arcsec::NAN,
+        dx: Arcsec::NAN,
+        dy: Arcsec::NAN,
+    };
 }
 
 /// An empty Earth orientation provider.
 ///
 /// This provider always returns [None] for all info. It is mostly
-/// used as a dummy argument to
-/// [ToScaleWith][crate::time::ToScaleWith] for conversions that do
-/// not need orientation data.
+/// used as a dummy argument to [ToScaleWith] for conversions that
+/// do not need orientation data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EmptyProvider;
 
@@ -68,3 +368,231 @@ impl Provider for EmptyProvider {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TAI - UTC steps from 36 s to 37 s at 2017-01-01
+    struct Step;
+
+    impl Step {
+        fn at() -> Epoch<UTC> {
+            Epoch::from_mjd(57_754.0)
+        }
+    }
+
+    impl Provider for Step {
+        fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+            TimeDelta::new(if *epoch < Self::at() { 36 } else { 37 }, 0)
+        }
+
+        fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+            let at = Self::at().transmute() + TimeDelta::new(37, 0)?;
+            TimeDelta::new(if *epoch < at { 36 } else { 37 }, 0)
+        }
+
+        fn ut1_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+            None
+        }
+
+        fn ut1_utc_for_ut1(&self, _epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+            None
+        }
+
+        fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+            alloc::vec![(Self::at(), TimeDelta::new(37, 0).unwrap())]
+        }
+    }
+
+    fn check_leap_seconds_between<P>(provider: &P)
+    where
+        P: Provider,
+    {
+        let before = Epoch::<UTC>::from_mjd(57_753.5);
+        let after = Epoch::<UTC>::from_mjd(57_754.5);
+        let later = Epoch::<UTC>::from_mjd(57_754.75);
+        let one = TimeDelta::new(1, 0);
+
+        assert_eq!(provider.leap_seconds_between(&before, &after), one);
+        assert_eq!(
+            provider.leap_seconds_between(&after, &before),
+            one.map(|d| -d)
+        );
+
+        // a change at the start counts, one at the end does not
+        assert_eq!(provider.leap_seconds_between(&Step::at(), &after), one);
+        assert_eq!(
+            provider.leap_seconds_between(&before, &Step::at()),
+            TimeDelta::new(0, 0)
+        );
+        assert_eq!(
+            provider.leap_seconds_between(&after, &later),
+            TimeDelta::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn leap_seconds_between_default() {
+        check_leap_seconds_between(&Step);
+
+        // without a list of leap seconds, no change is seen
+        struct Unlisted;
+
+        impl Provider for Unlisted {
+            fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+                Step.tai_utc_for_utc(epoch)
+            }
+
+            fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+                Step.tai_utc_for_tai(epoch)
+            }
+
+            fn ut1_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+                None
+            }
+
+            fn ut1_utc_for_ut1(&self, _epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+                None
+            }
+        }
+
+        let before = Epoch::<UTC>::from_mjd(57_753.5);
+        let after = Epoch::<UTC>::from_mjd(57_754.5);
+        assert_eq!(
+            Unlisted.leap_seconds_between(&before, &after),
+            TimeDelta::new(0, 0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn leap_seconds_between_with_list() {
+        let rows = [
+            (57_700.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_800.0, 37, 0.0),
+        ];
+        let provider = CelestrakProvider::from_rows(&rows);
+        assert_eq!(provider.leap_seconds().len(), 1);
+        check_leap_seconds_between(&provider);
+    }
+
+    #[test]
+    fn leap_seconds_between_missing_data() {
+        let a = Epoch::<UTC>::from_mjd(57_753.5);
+        let b = Epoch::<UTC>::from_mjd(57_754.5);
+        assert_eq!(EmptyProvider.leap_seconds_between(&a, &b), None);
+
+        let chain = ChainProvider::new(EmptyProvider, Step);
+        assert_eq!(chain.leap_seconds_between(&a, &b), TimeDelta::new(1, 0));
+    }
+
+    // UT1 - UTC and polar motion only, everything else is defaulted
+    struct Rotation;
+
+    impl Provider for Rotation {
+        fn tai_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+            TimeDelta::new(37, 0)
+        }
+
+        fn tai_utc_for_tai(&self, _epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+            TimeDelta::new(37, 0)
+        }
+
+        fn ut1_utc_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+            TimeDelta::new(0, 250_000_000)
+        }
+
+        fn ut1_utc_for_ut1(&self, _epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+            TimeDelta::new(0, 250_000_000)
+        }
+
+        fn polar_motion_for_utc(&self, _epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+            Some((Arcsec(0.125), Arcsec(0.25)))
+        }
+    }
+
+    #[test]
+    fn full_state_default() {
+        let epoch = Epoch::<UTC>::from_mjd(57_754.0);
+        let state = Rotation.full_state(&epoch).unwrap();
+        assert_eq!((state.x, state.y), (Arcsec(0.125), Arcsec(0.25)));
+        assert_eq!(state.ut1_utc, 0.25);
+        for unknown in [state.dpsi, state.deps, state.dx, state.dy] {
+            assert!(unknown.is_nan());
+        }
+        assert!(state.lod.is_nan());
+
+        // no UT1 - UTC, no state
+        assert_eq!(Step.full_state(&epoch), None);
+        assert_eq!(EmptyProvider.full_state(&epoch), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn full_state_celestrak() {
+        let provider = CelestrakProvider::builder()
+            .push(celestrak::Entry {
+                x: Arcsec(0.1),
+                dpsi: Arcsec(-0.05),
+                lod: 0.001,
+                ..celestrak::Entry::new(Epoch::from_mjd(57_000.0), 35, 0.5)
+            })
+            .entry(Epoch::from_mjd(57_001.0), 35, 0.3)
+            .build();
+
+        let state = provider.full_state(&Epoch::from_mjd(57_000.5)).unwrap();
+        assert!((state.ut1_utc - 0.4).abs() < 1e-9);
+        assert!((state.x.0 - 0.05).abs() < 1e-9);
+        assert!((state.dpsi.0 + 0.025).abs() < 1e-9);
+        assert!((state.lod - 0.0005).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gps_utc() {
+        assert_eq!(Step.gps_utc_for_utc(&Epoch::from_mjd(57_753.5)), Some(17));
+        assert_eq!(Step.gps_utc_for_utc(&Step::at()), Some(18));
+        assert_eq!(EmptyProvider.gps_utc_for_utc(&Step::at()), None);
+    }
+
+    #[test]
+    fn tai_utc_for_any_scale() {
+        use crate::time::{GPS, TT};
+
+        let before = TimeDelta::new(36, 0);
+        let after = TimeDelta::new(37, 0);
+        assert_eq!(Step.tai_utc_for(&Epoch::<UTC>::from_mjd(57_753.5)), before);
+        assert_eq!(Step.tai_utc_for(&Step::at()), after);
+
+        // TT and GPS reach TAI without the provider, and GPS is 19 s
+        // behind TAI, so its step is later
+        let tt = Step::at().transmute::<TT>();
+        assert_eq!(Step.tai_utc_for(&tt), before);
+        let gps = Epoch::<GPS>::from_mjd(57_754.0);
+        assert_eq!(Step.tai_utc_for(&gps), before);
+        let gps = Epoch::<GPS>::from_mjd(57_754.001);
+        assert_eq!(Step.tai_utc_for(&gps), after);
+        assert_eq!(EmptyProvider.tai_utc_for(&gps), None);
+    }
+
+    #[test]
+    fn boxed_and_borrowed() {
+        use alloc::boxed::Box;
+
+        // chosen at runtime, and still usable with generic conversions
+        let providers: [Box<dyn Provider>; 2] = [Box::new(Step), Box::new(EmptyProvider)];
+        let utc = Step::at();
+        assert_eq!(
+            utc.to_tai_with(&providers[0]),
+            Some(utc.transmute() + TimeDelta::new(37, 0).unwrap())
+        );
+        assert_eq!(utc.to_tai_with(&providers[1]), None);
+
+        // references forward too, including to trait objects
+        let borrowed: &dyn Provider = &Step;
+        assert_eq!(borrowed.gps_utc_for_utc(&utc), Some(18));
+        assert_eq!((&borrowed).tai_utc_for(&utc), TimeDelta::new(37, 0));
+        check_leap_seconds_between(&&Step);
+    }
+}
@@ -0,0 +1,239 @@
+//! Provider wrapper that smears leap seconds.
+
+use alloc::vec::Vec;
+
+use super::{EopState, LeapSecondsIter, Provider};
+use crate::time::{Epoch, TimeDelta, SECS_PER_DAY, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+/// A [Provider] that spreads each leap second over a window of time,
+/// instead of inserting it all at once.
+///
+/// Many networked clocks never show 23:59:60. Instead, they run
+/// slightly slow or fast for a while around each leap second, so
+/// that TAI - UTC changes smoothly. This provider models such a
+/// clock: inside the window around a leap second, TAI - UTC ramps
+/// linearly from its old value to its new one. Outside of these
+/// windows, it matches the inner provider exactly.
+///
+/// The leap seconds are taken from [Provider::leap_seconds] on the
+/// inner provider, when this provider is created. UT1 - UTC and the
+/// other parameters are passed through unchanged.
+///
+/// Only TAI - UTC is smeared. [Provider::leap_seconds],
+/// [Provider::leap_seconds_between], and [Provider::gps_utc_for_utc]
+/// describe the inner provider's whole-second schedule, so they
+/// always count whole leap seconds, even inside a window.
+#[derive(Debug, Clone)]
+pub struct SmearingProvider<P> {
+    inner: P,
+    before: TimeDelta<UTC>,
+    after: TimeDelta<UTC>,
+    // start of each window, TAI - UTC before it, and after it
+    windows: Vec<(Epoch<UTC>, TimeDelta<TAI>, TimeDelta<TAI>)>,
+}
+
+impl<P> SmearingProvider<P>
+where
+    P: Provider,
+{
+    /// Smear each leap second linearly from noon to noon, UTC.
+    pub fn new(inner: P) -> Self {
+        let half_day = match TimeDelta::new(SECS_PER_DAY as i64 / 2, 0) {
+            Some(delta) => delta,
+            None => unreachable!("half a day out of range"),
+        };
+
+        Self::with_window(inner, half_day, half_day)
+    }
+
+    /// Smear each leap second linearly, starting `before` the moment
+    /// it takes effect and ending `after` it.
+    ///
+    /// Windows should not overlap. Leap seconds where the inner
+    /// provider has no data at the start of the window are not
+    /// smeared.
+    pub fn with_window(inner: P, before: TimeDelta<UTC>, after: TimeDelta<UTC>) -> Self {
+        let windows = inner
+            .leap_seconds()
+            .into_iter()
+            .filter_map(|(at, new)| {
+                let start = at - before;
+                Some((start, inner.tai_utc_for_utc(&start)?, new))
+            })
+            .collect();
+
+        Self {
+            inner,
+            before,
+            after,
+            windows,
+        }
+    }
+}
+
+impl<P> SmearingProvider<P> {
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    // the total length of each window
+    fn span(&self) -> TimeDelta<UTC> {
+        self.before + self.after
+    }
+}
+
+impl<P> Provider for SmearingProvider<P>
+where
+    P: Provider,
+{
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        let span = self.span();
+        for &(start, old, new) in &self.windows {
+            if start <= *epoch && *epoch < start + span {
+                // old + (new - old) * elapsed / span
+                let elapsed = (*epoch - start).to_total_nanos();
+                let step = (new - old).to_total_nanos();
+                let ramp = step * elapsed / span.to_total_nanos();
+                return Some(old + TimeDelta::from_total_nanos(ramp)?);
+            }
+        }
+
+        self.inner.tai_utc_for_utc(epoch)
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        let span = self.span();
+        for &(start, old, new) in &self.windows {
+            let start_tai = start.transmute() + old;
+            let end_tai = (start + span).transmute() + new;
+            if start_tai <= *epoch && *epoch < end_tai {
+                // TAI runs through the window in span + step, not span
+                let elapsed = (*epoch - start_tai).to_total_nanos();
+                let step = (new - old).to_total_nanos();
+                let ramp = step * elapsed / (span.to_total_nanos() + step);
+                return Some(old + TimeDelta::from_total_nanos(ramp)?);
+            }
+        }
+
+        self.inner.tai_utc_for_tai(epoch)
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        self.inner.ut1_utc_for_utc(epoch)
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        self.inner.ut1_utc_for_ut1(epoch)
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner.polar_motion_for_utc(epoch)
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        self.inner.lod_for_utc(epoch)
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner.nutation_corrections_for_utc(epoch)
+    }
+
+    // broadcast GPS - UTC is never smeared
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        self.inner.gps_utc_for_utc(epoch)
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.inner.leap_seconds()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        self.inner.leap_seconds_iter()
+    }
+
+    // count whole leap seconds, like leap_seconds
+    fn leap_seconds_between(&self, a: &Epoch<UTC>, b: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        self.inner.leap_seconds_between(a, b)
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        self.inner.full_state(epoch)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::provider::CelestrakProvider;
+
+    // a leap second at the start of 2017
+    fn provider() -> SmearingProvider<CelestrakProvider> {
+        let rows = [
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_755.0, 37, 0.0),
+        ];
+        SmearingProvider::new(CelestrakProvider::from_rows(&rows))
+    }
+
+    fn seconds(delta: Option<TimeDelta<TAI>>) -> f64 {
+        delta.unwrap().to_seconds()
+    }
+
+    #[test]
+    fn ramps_across_window() {
+        let provider = provider();
+        let at = |mjd: f64| seconds(provider.tai_utc_for_utc(&Epoch::from_mjd(mjd)));
+
+        // outside the window, same as the inner provider
+        assert_eq!(at(57_753.25), 36.0);
+        assert_eq!(at(57_754.5), 37.0);
+        assert_eq!(at(57_754.75), 37.0);
+
+        // noon to noon, so a quarter, half, and three quarters through
+        assert!((at(57_753.75) - 36.25).abs() < 1e-9);
+        assert!((at(57_754.0) - 36.5).abs() < 1e-9);
+        assert!((at(57_754.25) - 36.75).abs() < 1e-9);
+
+        // smooth, and never decreasing
+        let mut last = at(57_753.5);
+        for i in 1..=100 {
+            let next = at(57_753.5 + i as f64 / 100.0);
+            assert!(next >= last && next - last < 0.011);
+            last = next;
+        }
+    }
+
+    #[test]
+    fn tai_and_utc_agree() {
+        let provider = provider();
+        for i in 0..=20 {
+            let utc = Epoch::<UTC>::from_mjd(57_753.4 + i as f64 / 16.0);
+            let tai = utc.to_tai_with(&provider).unwrap();
+            let back = tai.to_utc_with(&provider).unwrap();
+            assert!(back.approx_eq(&utc, TimeDelta::new(0, 10).unwrap()));
+        }
+    }
+
+    #[test]
+    fn whole_seconds_inside_window() {
+        let provider = provider();
+        let start = Epoch::<UTC>::from_mjd(57_753.25);
+        let middle = Epoch::<UTC>::from_mjd(57_754.25);
+        let end = Epoch::<UTC>::from_mjd(57_754.75);
+
+        assert_eq!(provider.gps_utc_for_utc(&start), Some(17));
+        assert_eq!(provider.gps_utc_for_utc(&middle), Some(18));
+        assert_eq!(
+            provider.leap_seconds_between(&start, &middle),
+            TimeDelta::new(1, 0)
+        );
+        assert_eq!(
+            provider.leap_seconds_between(&middle, &end),
+            TimeDelta::new(0, 0)
+        );
+        assert_eq!(provider.leap_seconds().len(), 1);
+    }
+}
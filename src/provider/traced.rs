@@ -0,0 +1,129 @@
+//! Provider wrapper that records whether it was used.
+
+use alloc::vec::Vec;
+
+use super::{EopState, LeapSecondsIter, Provider};
+use crate::time::{Epoch, TimeDelta, TAI, UT1, UTC};
+use crate::units::Arcsec;
+
+/// A [Provider] that forwards to another provider, and records
+/// whether it was ever queried.
+///
+/// This is used by
+/// [ToScaleWith::to_scale_traced_with][crate::time::ToScaleWith::to_scale_traced_with]
+/// to tell stateless conversions apart from ones that need Earth
+/// orientation data.
+#[derive(Debug)]
+pub struct TracedProvider<'a, P> {
+    inner: &'a P,
+    consulted: core::cell::Cell<bool>,
+}
+
+impl<'a, P> TracedProvider<'a, P> {
+    pub fn new(inner: &'a P) -> Self {
+        Self {
+            inner,
+            consulted: core::cell::Cell::new(false),
+        }
+    }
+
+    /// Has this provider been queried yet?
+    pub fn consulted(&self) -> bool {
+        self.consulted.get()
+    }
+
+    // mark this provider as queried, and return the inner provider
+    fn inner(&self) -> &'a P {
+        self.consulted.set(true);
+        self.inner
+    }
+}
+
+impl<P> Provider for TracedProvider<'_, P>
+where
+    P: Provider,
+{
+    fn tai_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<TAI>> {
+        self.inner().tai_utc_for_utc(epoch)
+    }
+
+    fn tai_utc_for_tai(&self, epoch: &Epoch<TAI>) -> Option<TimeDelta<TAI>> {
+        self.inner().tai_utc_for_tai(epoch)
+    }
+
+    fn ut1_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<TimeDelta<UT1>> {
+        self.inner().ut1_utc_for_utc(epoch)
+    }
+
+    fn ut1_utc_for_ut1(&self, epoch: &Epoch<UT1>) -> Option<TimeDelta<UT1>> {
+        self.inner().ut1_utc_for_ut1(epoch)
+    }
+
+    fn polar_motion_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner().polar_motion_for_utc(epoch)
+    }
+
+    fn lod_for_utc(&self, epoch: &Epoch<UTC>) -> Option<f64> {
+        self.inner().lod_for_utc(epoch)
+    }
+
+    fn nutation_corrections_for_utc(&self, epoch: &Epoch<UTC>) -> Option<(Arcsec, Arcsec)> {
+        self.inner().nutation_corrections_for_utc(epoch)
+    }
+
+    fn gps_utc_for_utc(&self, epoch: &Epoch<UTC>) -> Option<i64> {
+        self.inner().gps_utc_for_utc(epoch)
+    }
+
+    fn leap_seconds(&self) -> Vec<(Epoch<UTC>, TimeDelta<TAI>)> {
+        self.inner().leap_seconds()
+    }
+
+    fn leap_seconds_iter(&self) -> LeapSecondsIter<'_> {
+        self.inner().leap_seconds_iter()
+    }
+
+    fn full_state(&self, epoch: &Epoch<UTC>) -> Option<EopState> {
+        self.inner().full_state(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::EmptyProvider;
+    use crate::time::{ToScaleWith, TT};
+
+    #[test]
+    fn consulted() {
+        let traced = TracedProvider::new(&EmptyProvider);
+        assert!(!traced.consulted());
+        assert_eq!(traced.tai_utc_for_utc(&Epoch::from_mjd(51_544.0)), None);
+        assert!(traced.consulted());
+    }
+
+    #[test]
+    fn stateless_conversion() {
+        let epoch = Epoch::<TAI>::from_mjd(51_544.0);
+        let (tt, consulted) =
+            ToScaleWith::<TT>::to_scale_traced_with(&epoch, &EmptyProvider).unwrap();
+        assert_eq!(tt, epoch.to_tt());
+        assert!(!consulted);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn provider_conversion() {
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (51_544.0, 32, 0.0),
+            (51_545.0, 32, 0.0),
+        ]);
+        let epoch = Epoch::<TAI>::from_mjd(51_544.5);
+        let (utc, consulted) = ToScaleWith::<UTC>::to_scale_traced_with(&epoch, &provider).unwrap();
+        assert_eq!(utc, epoch.to_utc_with(&provider).unwrap());
+        assert!(consulted);
+
+        let missing = ToScaleWith::<UTC>::to_scale_traced_with(&epoch, &EmptyProvider);
+        assert_eq!(missing, None);
+    }
+}
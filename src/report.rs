@@ -0,0 +1,82 @@
+//! Tabular output of [Epoch]s in several [Scale][crate::time::Scale]s.
+
+use crate::provider::Provider;
+use crate::time::{Epoch, TAI};
+
+// one CSV cell, blank if the conversion failed
+fn cell<S>(epoch: Option<Epoch<S>>) -> String {
+    epoch
+        .and_then(|e| e.try_to_name())
+        .map(|name| name.to_string())
+        .unwrap_or_default()
+}
+
+/// Write a CSV table showing each [TAI] epoch in every supported
+/// timescale.
+///
+/// The table has a header row, followed by one row per epoch with
+/// the columns `TAI`, `TT`, `GPS`, `UTC`, and `UT1`. Cells are left
+/// blank where the provider has no data for a conversion.
+pub fn write_scale_table<W, P>(mut w: W, epochs: &[Epoch<TAI>], provider: &P) -> std::io::Result<()>
+where
+    W: std::io::Write,
+    P: Provider,
+{
+    writeln!(w, "TAI,TT,GPS,UTC,UT1")?;
+
+    for epoch in epochs {
+        writeln!(
+            w,
+            "{},{},{},{},{}",
+            cell(Some(*epoch)),
+            cell(Some(epoch.to_tt())),
+            cell(Some(epoch.to_gps())),
+            cell(epoch.to_utc_with(provider)),
+            cell(epoch.to_ut1_with(provider)),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{CelestrakProvider, EmptyProvider};
+
+    #[test]
+    fn scale_table() {
+        let provider = CelestrakProvider::from_rows(&[(57_905.0, 37, 0.25), (57_906.0, 37, 0.25)]);
+        let epochs = [
+            Epoch::<TAI>::from_mjd(57_905.5),
+            Epoch::<TAI>::from_mjd(58_000.0),
+        ];
+
+        let mut csv = Vec::new();
+        write_scale_table(&mut csv, &epochs, &provider).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next(), Some("TAI,TT,GPS,UTC,UT1"));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "2017-06-01 12:00:00,2017-06-01 12:00:32.184,2017-06-01 11:59:41,\
+                 2017-06-01 11:59:23,2017-06-01 11:59:23.250"
+            )
+        );
+        // no data for UTC or UT1
+        assert_eq!(
+            lines.next(),
+            Some("2017-09-04 00:00:00,2017-09-04 00:00:32.184,2017-09-03 23:59:41,,")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn scale_table_empty() {
+        let mut csv = Vec::new();
+        write_scale_table(&mut csv, &[], &EmptyProvider).unwrap();
+        assert_eq!(csv, b"TAI,TT,GPS,UTC,UT1\n");
+    }
+}
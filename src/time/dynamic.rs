@@ -0,0 +1,225 @@
+use super::{Epoch, ScaleKind, ToScaleWith, GPS, TAI, TCB, TCG, TDB, TT, UT1, UTC};
+use crate::provider::Provider;
+
+/// An [Epoch] in a [Scale][super::Scale] chosen at runtime.
+///
+/// Each variant holds an [Epoch] in the scale of the same name. This
+/// allows epochs in different scales to be stored together, such as
+/// in a single [Vec][alloc::vec::Vec].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynEpoch {
+    TAI(Epoch<TAI>),
+    TT(Epoch<TT>),
+    GPS(Epoch<GPS>),
+    UTC(Epoch<UTC>),
+    UT1(Epoch<UT1>),
+    TDB(Epoch<TDB>),
+    TCG(Epoch<TCG>),
+    TCB(Epoch<TCB>),
+}
+
+impl DynEpoch {
+    /// Create a [DynEpoch] from a name in the given scale, as in
+    /// [Epoch::from_name].
+    pub fn from_name(scale: ScaleKind, name: chrono::NaiveDateTime) -> Self {
+        match scale {
+            ScaleKind::TAI => Self::TAI(Epoch::from_name(name)),
+            ScaleKind::TT => Self::TT(Epoch::from_name(name)),
+            ScaleKind::GPS => Self::GPS(Epoch::from_name(name)),
+            ScaleKind::UTC => Self::UTC(Epoch::from_name(name)),
+            ScaleKind::UT1 => Self::UT1(Epoch::from_name(name)),
+            ScaleKind::TDB => Self::TDB(Epoch::from_name(name)),
+            ScaleKind::TCG => Self::TCG(Epoch::from_name(name)),
+            ScaleKind::TCB => Self::TCB(Epoch::from_name(name)),
+        }
+    }
+
+    /// The name of this epoch in its own scale, as in
+    /// [Epoch::try_to_name].
+    pub fn try_to_name(&self) -> Option<chrono::NaiveDateTime> {
+        match self {
+            Self::TAI(epoch) => epoch.try_to_name(),
+            Self::TT(epoch) => epoch.try_to_name(),
+            Self::GPS(epoch) => epoch.try_to_name(),
+            Self::UTC(epoch) => epoch.try_to_name(),
+            Self::UT1(epoch) => epoch.try_to_name(),
+            Self::TDB(epoch) => epoch.try_to_name(),
+            Self::TCG(epoch) => epoch.try_to_name(),
+            Self::TCB(epoch) => epoch.try_to_name(),
+        }
+    }
+
+    /// The scale of this epoch.
+    pub fn scale(&self) -> ScaleKind {
+        match self {
+            Self::TAI(_) => ScaleKind::TAI,
+            Self::TT(_) => ScaleKind::TT,
+            Self::GPS(_) => ScaleKind::GPS,
+            Self::UTC(_) => ScaleKind::UTC,
+            Self::UT1(_) => ScaleKind::UT1,
+            Self::TDB(_) => ScaleKind::TDB,
+            Self::TCG(_) => ScaleKind::TCG,
+            Self::TCB(_) => ScaleKind::TCB,
+        }
+    }
+
+    /// Convert to another scale, using an Earth orientation
+    /// [Provider].
+    ///
+    /// Returns [None] if the conversion fails. See [ToScaleWith].
+    pub fn to_scale<P>(&self, scale: ScaleKind, provider: &P) -> Option<Self>
+    where
+        P: Provider,
+    {
+        match self {
+            Self::TAI(epoch) => convert_from(epoch, scale, provider),
+            Self::TT(epoch) => convert_from(epoch, scale, provider),
+            Self::GPS(epoch) => convert_from(epoch, scale, provider),
+            Self::UTC(epoch) => convert_from(epoch, scale, provider),
+            Self::UT1(epoch) => convert_from(epoch, scale, provider),
+            Self::TDB(epoch) => convert_from(epoch, scale, provider),
+            Self::TCG(epoch) => convert_from(epoch, scale, provider),
+            Self::TCB(epoch) => convert_from(epoch, scale, provider),
+        }
+    }
+}
+
+// second half of to_scale, once the source scale is known
+fn convert_from<S, P>(epoch: &Epoch<S>, scale: ScaleKind, provider: &P) -> Option<DynEpoch>
+where
+    P: Provider,
+    Epoch<S>: ToScaleWith<TAI>
+        + ToScaleWith<TT>
+        + ToScaleWith<GPS>
+        + ToScaleWith<UTC>
+        + ToScaleWith<UT1>
+        + ToScaleWith<TDB>
+        + ToScaleWith<TCG>
+        + ToScaleWith<TCB>,
+{
+    Some(match scale {
+        ScaleKind::TAI => DynEpoch::TAI(epoch.convert(provider)?),
+        ScaleKind::TT => DynEpoch::TT(epoch.convert(provider)?),
+        ScaleKind::GPS => DynEpoch::GPS(epoch.convert(provider)?),
+        ScaleKind::UTC => DynEpoch::UTC(epoch.convert(provider)?),
+        ScaleKind::UT1 => DynEpoch::UT1(epoch.convert(provider)?),
+        ScaleKind::TDB => DynEpoch::TDB(epoch.convert(provider)?),
+        ScaleKind::TCG => DynEpoch::TCG(epoch.convert(provider)?),
+        ScaleKind::TCB => DynEpoch::TCB(epoch.convert(provider)?),
+    })
+}
+
+/// Convert an epoch between two [Scale][super::Scale]s chosen at
+/// runtime, using an Earth orientation [Provider].
+///
+/// The epoch is given and returned as a name, as in
+/// [Epoch::from_name] and [Epoch::to_name]. Returns [None] if the
+/// conversion fails, or if the result cannot be named. See
+/// [DynEpoch].
+pub fn convert_dynamic<P>(
+    name: chrono::NaiveDateTime,
+    from: ScaleKind,
+    to: ScaleKind,
+    provider: &P,
+) -> Option<chrono::NaiveDateTime>
+where
+    P: Provider,
+{
+    DynEpoch::from_name(from, name)
+        .to_scale(to, provider)?
+        .try_to_name()
+}
+
+/// Includes the scale name, as for [Epoch].
+impl core::fmt::Display for DynEpoch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::TAI(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::TT(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::GPS(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::UTC(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::UT1(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::TDB(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::TCG(epoch) => f.write_fmt(format_args!("{}", epoch)),
+            Self::TCB(epoch) => f.write_fmt(format_args!("{}", epoch)),
+        }
+    }
+}
+
+macro_rules! impl_from_epoch {
+    ($($Scale:ident),*) => {
+        $(
+            impl core::convert::From<Epoch<$Scale>> for DynEpoch {
+                fn from(epoch: Epoch<$Scale>) -> Self {
+                    Self::$Scale(epoch)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_epoch!(TAI, TT, GPS, UTC, UT1, TDB, TCG, TCB);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Scale;
+    use alloc::string::ToString;
+
+    fn name(hour: u32, minute: u32, second: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2017, 6, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn scale_names() {
+        for kind in ScaleKind::ALL {
+            assert_eq!(ScaleKind::from_name(kind.name()), Some(kind));
+            let lower = kind.name().to_ascii_lowercase();
+            assert_eq!(ScaleKind::from_name(&lower), Some(kind));
+        }
+
+        assert_eq!(ScaleKind::UT1.name(), UT1::NAME);
+        assert_eq!(ScaleKind::from_name("UTC "), None);
+        assert_eq!(ScaleKind::from_name(""), None);
+    }
+
+    #[test]
+    fn dyn_epoch_names() {
+        for kind in ScaleKind::ALL {
+            let epoch = DynEpoch::from_name(kind, name(12, 0, 0));
+            assert_eq!(epoch.scale(), kind);
+            assert_eq!(epoch.try_to_name(), Some(name(12, 0, 0)));
+            assert!(epoch.to_string().ends_with(kind.name()));
+        }
+
+        let utc = Epoch::<UTC>::from_name(name(12, 0, 0));
+        assert_eq!(DynEpoch::from(utc), DynEpoch::UTC(utc));
+        assert_eq!(DynEpoch::from(utc).to_string(), utc.to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dynamic_utc_to_tai() {
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (57_905.0, 37, 0.0),
+            (57_906.0, 37, 0.0),
+        ]);
+
+        let tai = convert_dynamic(name(0, 0, 0), ScaleKind::UTC, ScaleKind::TAI, &provider);
+        assert_eq!(tai, Some(name(0, 0, 37)));
+        let gps = convert_dynamic(name(0, 0, 0), ScaleKind::UTC, ScaleKind::GPS, &provider);
+        assert_eq!(gps, Some(name(0, 0, 18)));
+
+        // no TAI - UTC without data
+        let empty = convert_dynamic(
+            name(0, 0, 0),
+            ScaleKind::UTC,
+            ScaleKind::TAI,
+            &crate::provider::EmptyProvider,
+        );
+        assert_eq!(empty, None);
+    }
+}
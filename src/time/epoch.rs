@@ -1,9 +1,78 @@
 //! Constants that refer to specific epochs.
 
-use super::name::name_gregorian;
+use super::name::{name_gregorian, name_gregorian_nanos};
 use super::{Epoch, TT};
 
+/// J1900.0
+///
+/// December 31, 1899 CE at 12:00 TT (proleptic Gregorian), often
+/// written January 0.5, 1900, or JD 2415020.0 TT.
+pub const J1900: Epoch<TT> = Epoch::from_name(name_gregorian(1899, 12, 31, 12, 0, 0));
+
+/// J1950.0
+///
+/// January 1, 1950 CE at 00:00 TT (proleptic Gregorian), or
+/// JD 2433282.5 TT.
+pub const J1950: Epoch<TT> = Epoch::from_name(name_gregorian(1950, 1, 1, 0, 0, 0));
+
 /// J2000.0
 ///
 /// January 1, 2000 CE at 12:00 TT (proleptic Gregorian).
 pub const J2000: Epoch<TT> = Epoch::from_name(name_gregorian(2000, 1, 1, 12, 0, 0));
+
+/// B1950.0
+///
+/// December 31, 1949 CE at 22:09:46.86192 TT (proleptic Gregorian),
+/// or JD 2433282.42345905 TT.
+pub const B1950: Epoch<TT> =
+    Epoch::from_name(name_gregorian_nanos(1949, 12, 31, 22, 9, 46, 861_920_000));
+
+/// The origins of specific time scales and formats.
+pub mod origins {
+    use super::super::name::{name_gregorian, GPS_WEEK_0, UNIX_EPOCH_0};
+    use super::super::{Epoch, GPS, TAI, UTC};
+
+    /// The GPS epoch.
+    ///
+    /// January 6, 1980 CE at 00:00 GPS (proleptic Gregorian), which
+    /// is also 00:00 UTC. GPS week 0 starts here.
+    pub const GPS_EPOCH: Epoch<GPS> = Epoch::from_name(GPS_WEEK_0);
+
+    /// The origin of the TT, TCG, and TCB rate definitions.
+    ///
+    /// January 1, 1977 CE at 00:00 TAI (proleptic Gregorian). At this
+    /// instant, TCG and TCB both read 00:00:32.184, the same as TT.
+    pub const TAI_1977: Epoch<TAI> = Epoch::from_name(name_gregorian(1977, 1, 1, 0, 0, 0));
+
+    /// The Unix epoch.
+    ///
+    /// January 1, 1970 CE at 00:00 UTC (proleptic Gregorian).
+    pub const UNIX_EPOCH: Epoch<UTC> = Epoch::from_name(UNIX_EPOCH_0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn julian_epochs() {
+        assert_eq!(J1900.jd(), 2_415_020.0);
+        assert_eq!(J1950.jd(), 2_433_282.5);
+        assert_eq!(J2000.jd(), 2_451_545.0);
+
+        // each is a whole number of Julian years from J2000
+        assert_eq!(J1900.julian_centuries_since_j2000(), -1.0);
+        assert_eq!(J1950.julian_centuries_since_j2000(), -0.5);
+    }
+
+    #[test]
+    fn origins() {
+        use super::origins::{GPS_EPOCH, TAI_1977, UNIX_EPOCH};
+
+        assert_eq!(GPS_EPOCH.mjd(), 44_244.0);
+        assert_eq!(GPS_EPOCH.to_week_tow(), (0, 0.0));
+        assert_eq!(TAI_1977.mjd(), 43_144.0);
+        assert_eq!(UNIX_EPOCH.mjd(), 40_587.0);
+        assert_eq!(UNIX_EPOCH.to_unix_seconds(), 0.0);
+    }
+}
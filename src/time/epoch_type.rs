@@ -1,8 +1,28 @@
-use super::name::{FRAMESHIFT_0, JULIAN_DAY_0, MODIFIED_JULIAN_DAY_0};
-use super::{Scale, TimeDelta, ToScale, ToScaleWith, GPS, TAI, TT, UT1, UTC};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use super::epoch::{B1950, J2000};
+use super::name::{
+    gregorian_to_julian, julian_to_gregorian, FRAMESHIFT_0, JULIAN_DAY_0, MODIFIED_JULIAN_DAY_0,
+    UNIX_EPOCH_0,
+};
+use super::{
+    Scale, TimeDelta, ToScale, ToScaleWith, DAYS_PER_JULIAN_CENTURY, GPS, NANOS_PER_SEC,
+    SECS_PER_DAY, TAI, TCB, TCG, TDB, TT, UT1, UTC,
+};
 use crate::provider::Provider;
 
+// length of a Besselian (tropical) year at B1900, in days
+const DAYS_PER_BESSELIAN_YEAR: f64 = 365.242198781;
+
 /// A specific instant in time, measured in a specific [Scale].
+///
+/// Epochs before [FRAMESHIFT_0] are supported, including dates BCE.
+///
+/// Epochs in the same [Scale] are totally ordered, so [Ord::min],
+/// [Ord::max], and [Ord::clamp] work as expected. Epochs in
+/// different scales cannot be compared without converting one of
+/// them first.
 pub struct Epoch<Scale> {
     // measures delta from Jan 1, 1900 00:00:00.0 *in this scale*
     // so Jan 1, 1900 00:00 TAI is encoded as TimeDelta<TAI>::new(0, 0)
@@ -12,6 +32,18 @@ pub struct Epoch<Scale> {
 }
 
 impl<S> Epoch<S> {
+    /// The earliest representable [Epoch].
+    ///
+    /// This is useful as a sentinel, but is far outside the range of
+    /// [to_name][Self::to_name].
+    pub const MIN: Self = Self::from_frameshift(TimeDelta::MIN);
+
+    /// The latest representable [Epoch].
+    ///
+    /// This is useful as a sentinel, but is far outside the range of
+    /// [to_name][Self::to_name].
+    pub const MAX: Self = Self::from_frameshift(TimeDelta::MAX);
+
     pub const fn from_frameshift(delta: TimeDelta<S>) -> Self {
         Self { delta }
     }
@@ -42,6 +74,12 @@ impl<S> Epoch<S> {
         FRAMESHIFT_0 + self.delta.to_chrono()
     }
 
+    /// Like [to_name][Self::to_name], but returns [None] instead of
+    /// panicking if the result is outside chrono's supported range.
+    pub fn try_to_name(&self) -> Option<chrono::NaiveDateTime> {
+        FRAMESHIFT_0.checked_add_signed(self.delta.to_chrono())
+    }
+
     pub fn from_julian_day(delta: TimeDelta<S>) -> Self {
         Self::from_name_delta(&JULIAN_DAY_0, delta)
     }
@@ -57,6 +95,216 @@ impl<S> Epoch<S> {
     pub fn to_modified_julian_day(&self) -> TimeDelta<S> {
         self.to_name_delta(&MODIFIED_JULIAN_DAY_0)
     }
+
+    /// Create an [Epoch] from a fractional Julian date in this
+    /// [Scale].
+    pub fn from_jd(jd: f64) -> Self {
+        Self::from_julian_day(TimeDelta::from_days(jd))
+    }
+
+    /// The fractional Julian date in this [Scale].
+    ///
+    /// A single [f64] only resolves about 20 us near the present day.
+    pub fn jd(&self) -> f64 {
+        self.to_julian_day().to_days()
+    }
+
+    /// Create an [Epoch] from a Julian date split into two parts,
+    /// whose sum is the full date.
+    ///
+    /// Any split works, but precision is best when one part holds
+    /// the whole days and the other the fraction, as returned by
+    /// [jd_two_part][Self::jd_two_part]. The result is rounded to
+    /// the nearest nanosecond. Returns [None] if it is out of range.
+    pub fn from_jd_two_part(jd1: f64, jd2: f64) -> Option<Self> {
+        let (day1, day2) = (libm::floor(jd1), libm::floor(jd2));
+        let fraction = (jd1 - day1) + (jd2 - day2);
+        let nanos = libm::round(fraction * SECS_PER_DAY as f64 * NANOS_PER_SEC as f64);
+
+        let days = day1 + day2;
+        if !days.is_finite() || days.abs() >= i64::MAX as f64 {
+            return None;
+        }
+
+        Some(Self::from_julian_day(TimeDelta::from_day_nanos(
+            days as i64,
+            nanos as u64,
+        )?))
+    }
+
+    /// Split the Julian date in this [Scale] into a whole day number
+    /// and a fraction of a day, in the style of SOFA.
+    ///
+    /// The fraction is in `[0, 1)`. Unlike [jd][Self::jd], this keeps
+    /// nanosecond precision near the present day.
+    pub fn jd_two_part(&self) -> (f64, f64) {
+        let (days, nanos) = self.to_julian_day().to_day_nanos();
+        (
+            days as f64,
+            nanos as f64 / (SECS_PER_DAY as f64 * NANOS_PER_SEC as f64),
+        )
+    }
+
+    /// Create an [Epoch] from a fractional Modified Julian Date in
+    /// this [Scale].
+    pub fn from_mjd(mjd: f64) -> Self {
+        Self::from_modified_julian_day(TimeDelta::from_days(mjd))
+    }
+
+    /// The fractional Modified Julian Date in this [Scale].
+    pub fn mjd(&self) -> f64 {
+        self.to_modified_julian_day().to_days()
+    }
+
+    /// Create an [Epoch] from an integer MJD and nanoseconds into
+    /// that day, exactly.
+    ///
+    /// Returns [None] if the result is out of range. See
+    /// [to_mjd_day_nanos][Self::to_mjd_day_nanos].
+    pub fn from_mjd_day_nanos(day: i64, nanos: u64) -> Option<Self> {
+        Some(Self::from_modified_julian_day(TimeDelta::from_day_nanos(
+            day, nanos,
+        )?))
+    }
+
+    /// Split this [Epoch] into an integer MJD and nanoseconds into
+    /// that day, exactly.
+    ///
+    /// This is suitable for lossless storage, and sorts correctly.
+    /// The nanoseconds are always less than 86,400 s. For [UTC], a
+    /// day containing a leap second is 86,401 s long, but [Epoch]
+    /// cannot represent the leap second itself, so this still holds.
+    /// Stored leap seconds with nanoseconds past 86,400 s are read by
+    /// [from_mjd_day_nanos][Self::from_mjd_day_nanos] as part of the
+    /// following day.
+    pub fn to_mjd_day_nanos(&self) -> (i64, u64) {
+        self.to_modified_julian_day().to_day_nanos()
+    }
+
+    /// Add a delta to this [Epoch], returning [None] on overflow.
+    pub fn checked_add(self, delta: TimeDelta<S>) -> Option<Self> {
+        Some(Self::from_frameshift(self.delta.checked_add(delta)?))
+    }
+
+    /// Subtract a delta from this [Epoch], returning [None] on
+    /// overflow.
+    pub fn checked_sub(self, delta: TimeDelta<S>) -> Option<Self> {
+        Some(Self::from_frameshift(self.delta.checked_sub(delta)?))
+    }
+
+    /// Iterate from `start` to `end` in increments of `step`.
+    ///
+    /// This yields `start`, `start + step`, and so on, up to and
+    /// including `end` if it lands on it exactly. If `step` is
+    /// negative, this counts down from `start` to `end` instead. If
+    /// `step` is zero, or points away from `end`, this is empty.
+    pub fn range(start: Self, end: Self, step: TimeDelta<S>) -> EpochRange<S> {
+        EpochRange {
+            next: if step.is_zero() { None } else { Some(start) },
+            end,
+            step,
+        }
+    }
+
+    /// Whether this [Epoch] is within `tolerance` of `other`,
+    /// inclusive.
+    ///
+    /// This is useful for comparing results of conversions that go
+    /// through floating point, such as to [UT1] or [TDB]. The sign of
+    /// `tolerance` is ignored.
+    pub fn approx_eq(&self, other: &Self, tolerance: TimeDelta<S>) -> bool {
+        self.delta.approx_eq(&other.delta, tolerance)
+    }
+
+    /// Round to the nearest whole second in this [Scale].
+    ///
+    /// This is [round_to][Self::round_to] with a granularity of one
+    /// second, so halfway cases round to the even second. Since TAI -
+    /// UTC is a whole number of seconds, this lands on the same
+    /// instants in [UTC] and [TAI]. To snap to a leap second such as
+    /// `23:59:60`, which [UTC] cannot represent, round in [TAI] and
+    /// then use [to_utc_gregorian_leap][Epoch::to_utc_gregorian_leap].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rounded epoch is out of range, which can only
+    /// happen within a second of [Epoch::MIN] or [Epoch::MAX].
+    pub fn snap_to_second(&self) -> Self {
+        self.round_to(TimeDelta::new(1, 0).unwrap())
+    }
+
+    /// Round to the nearest multiple of `granularity` since
+    /// [FRAMESHIFT_0].
+    ///
+    /// Halfway cases round to the even multiple. The sign of
+    /// `granularity` is ignored, and a zero `granularity` returns
+    /// this epoch unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rounded epoch is out of range.
+    pub fn round_to(&self, granularity: TimeDelta<S>) -> Self {
+        self.snap_to(granularity, |quotient, remainder, step| {
+            if 2 * remainder > step || (2 * remainder == step && quotient % 2 != 0) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        })
+    }
+
+    /// Round down to a multiple of `granularity` since
+    /// [FRAMESHIFT_0].
+    ///
+    /// This always rounds towards the past, even before
+    /// [FRAMESHIFT_0]. The sign of `granularity` is ignored, and a
+    /// zero `granularity` returns this epoch unchanged.
+    pub fn truncate_to(&self, granularity: TimeDelta<S>) -> Self {
+        self.snap_to(granularity, |quotient, _, _| quotient)
+    }
+
+    // snap to a multiple of granularity, given a function that picks
+    // the multiple from the floored quotient, remainder, and step
+    fn snap_to<F>(&self, granularity: TimeDelta<S>, pick: F) -> Self
+    where
+        F: FnOnce(i128, i128, i128) -> i128,
+    {
+        let step = granularity.to_total_nanos().abs();
+        if step == 0 {
+            return *self;
+        }
+
+        let total = self.delta.to_total_nanos();
+        let snapped = pick(total.div_euclid(step), total.rem_euclid(step), step) * step;
+        match TimeDelta::from_total_nanos(snapped) {
+            Some(delta) => Self::from_frameshift(delta),
+            None => panic!("rounded epoch out of range"),
+        }
+    }
+}
+
+impl<S> Epoch<S>
+where
+    S: Scale,
+{
+    /// Format as an RFC 3339 style timestamp without an offset, such
+    /// as `2000-01-01T12:00:00.000000000`.
+    ///
+    /// There is no offset since this [Epoch] is measured in an
+    /// abstract [Scale]. All nine digits of nanoseconds are always
+    /// written, so no precision is lost.
+    pub fn to_rfc3339(&self) -> String {
+        self.to_name().format("%Y-%m-%dT%H:%M:%S%.9f").to_string()
+    }
+
+    /// Format like [to_rfc3339][Self::to_rfc3339], with the scale
+    /// appended in brackets, such as
+    /// `2000-01-01T12:00:00.000000000[TT]`.
+    ///
+    /// This can be parsed with [FromStr][core::str::FromStr].
+    pub fn to_iso8601_with_scale(&self) -> String {
+        format!("{}[{}]", self.to_rfc3339(), S::NAME)
+    }
 }
 
 macro_rules! to_scale_helpers {
@@ -97,6 +345,219 @@ impl<S> Epoch<S> {
     to_scale_helpers!(GPS, to_gps_with, to_gps);
     to_scale_helpers!(UTC, to_utc_with);
     to_scale_helpers!(UT1, to_ut1_with);
+    to_scale_helpers!(TDB, to_tdb_with, to_tdb);
+    to_scale_helpers!(TCG, to_tcg_with, to_tcg);
+    to_scale_helpers!(TCB, to_tcb_with, to_tcb);
+
+    /// Convert to any [Scale], using an orientation provider.
+    ///
+    /// This is [ToScaleWith::to_scale_with], with the target scale
+    /// first so it can be named with a turbofish, such as
+    /// `epoch.convert::<TT, _>(&provider)`.
+    pub fn convert<T, P>(&self, provider: &P) -> Option<Epoch<T>>
+    where
+        P: Provider,
+        Self: ToScaleWith<T>,
+    {
+        self.to_scale_with(provider)
+    }
+
+    /// Convert to any [Scale], statelessly.
+    ///
+    /// This is [ToScale::to_scale], with the target scale as a type
+    /// parameter, such as `epoch.convert_to::<GPS>()`.
+    pub fn convert_to<T>(&self) -> Epoch<T>
+    where
+        Self: ToScale<T>,
+    {
+        self.to_scale()
+    }
+
+    /// The time from `other` to this [Epoch], where `other` may be in
+    /// a different [Scale].
+    ///
+    /// `other` is first converted into this scale using an
+    /// orientation provider, so this returns [None] if that
+    /// conversion fails. See [ToScaleWith].
+    pub fn diff_with<T, P>(&self, other: &Epoch<T>, provider: &P) -> Option<TimeDelta<S>>
+    where
+        P: Provider,
+        Epoch<T>: ToScaleWith<S>,
+    {
+        Some(*self - other.to_scale_with(provider)?)
+    }
+
+    /// Compare this [Epoch] with `other` as instants in time, where
+    /// `other` may be in a different [Scale].
+    ///
+    /// `other` is first converted into this scale using an
+    /// orientation provider, so this returns [None] if that
+    /// conversion fails. Within a single scale, use [Ord] instead.
+    pub fn cmp_instant<T, P>(&self, other: &Epoch<T>, provider: &P) -> Option<core::cmp::Ordering>
+    where
+        P: Provider,
+        Epoch<T>: ToScaleWith<S>,
+    {
+        Some(self.cmp(&other.to_scale_with(provider)?))
+    }
+}
+
+impl Epoch<TT> {
+    /// Julian centuries of TT since `other`.
+    pub fn julian_centuries_since(&self, other: &Self) -> f64 {
+        // whole days and a fraction of a day, as in earth_rotation_angle
+        let (days, nanos) = (*self - *other).to_day_nanos();
+        let fraction = nanos as f64 / (SECS_PER_DAY as f64 * NANOS_PER_SEC as f64);
+        (days as f64 + fraction) / DAYS_PER_JULIAN_CENTURY as f64
+    }
+
+    /// Julian centuries of TT since [J2000].
+    ///
+    /// This is the time argument `T` used by most astronomical
+    /// series, such as precession and nutation.
+    pub fn julian_centuries_since_j2000(&self) -> f64 {
+        self.julian_centuries_since(&J2000)
+    }
+
+    /// Julian millennia of TT since [J2000].
+    pub fn julian_millennia_since_j2000(&self) -> f64 {
+        self.julian_centuries_since_j2000() / 10.0
+    }
+
+    /// Create an [Epoch] from a Besselian year, such as 1950.0 for
+    /// [B1950].
+    pub fn from_besselian_year(year: f64) -> Self {
+        B1950 + TimeDelta::from_days((year - 1950.0) * DAYS_PER_BESSELIAN_YEAR)
+    }
+
+    /// The Besselian year of this epoch, such as 1950.0 for [B1950].
+    ///
+    /// This is `1900.0 + (JD_TT - 2415020.31352) / 365.242198781`.
+    pub fn to_besselian_year(&self) -> f64 {
+        1950.0 + (*self - B1950).to_days() / DAYS_PER_BESSELIAN_YEAR
+    }
+}
+
+impl Epoch<TAI> {
+    /// SI seconds elapsed since `origin`.
+    ///
+    /// TAI ticks uniformly, so this is a true count of SI seconds. The
+    /// same subtraction in [UTC] would not be, since it ignores leap
+    /// seconds.
+    pub fn si_seconds_since(&self, origin: &Self) -> f64 {
+        (*self - *origin).to_seconds()
+    }
+
+    /// SI nanoseconds elapsed since `origin`, exactly.
+    ///
+    /// See [si_seconds_since][Self::si_seconds_since].
+    pub fn si_nanos_since(&self, origin: &Self) -> i128 {
+        (*self - *origin).to_total_nanos()
+    }
+
+    /// TAI - UTC at this epoch, using an Earth orientation
+    /// [Provider].
+    ///
+    /// This is [Provider::tai_utc_for_tai].
+    pub fn tai_minus_utc<P>(&self, provider: &P) -> Option<TimeDelta<TAI>>
+    where
+        P: Provider,
+    {
+        provider.tai_utc_for_tai(self)
+    }
+
+    /// Create an [Epoch] from a UTC calendar date and time that may
+    /// fall on a leap second, using an Earth orientation [Provider].
+    ///
+    /// [Epoch]s in [UTC] cannot represent a leap second such as
+    /// `2016-12-31 23:59:60`, so the result is in [TAI] instead. A
+    /// `second` of 60 is only accepted at the end of a minute where
+    /// the provider shows TAI - UTC increasing by one second.
+    ///
+    /// This is a constructor on `Epoch<TAI>`, not `Epoch<UTC>`, for
+    /// the same reason: in [UTC], the leap second and the first second
+    /// of the next minute are the same [Epoch], so there would be
+    /// nothing to return for `:60`, and nothing for
+    /// [to_utc_gregorian_leap][Self::to_utc_gregorian_leap] to tell
+    /// apart.
+    ///
+    /// Returns [None] if the date or time is invalid, or if the
+    /// provider has no data for it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_utc_gregorian_leap<P>(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        nanos: u32,
+        provider: &P,
+    ) -> Option<Self>
+    where
+        P: Provider,
+    {
+        let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+        let fraction = TimeDelta::new(0, nanos).filter(|_| nanos < NANOS_PER_SEC)?;
+
+        if second != 60 {
+            let name = date.and_hms_opt(hour, minute, second)?;
+            return Some(Epoch::<UTC>::from_name(name).to_tai_with(provider)? + fraction);
+        }
+
+        // the leap second is the one after :59, and before the next minute
+        let one = TimeDelta::new(1, 0)?;
+        let before = Epoch::<UTC>::from_name(date.and_hms_opt(hour, minute, 59)?);
+        let after = before + one.transmute();
+        let step = provider.tai_utc_for_utc(&after)? - provider.tai_utc_for_utc(&before)?;
+        if step != one {
+            return None;
+        }
+
+        Some(before.to_tai_with(provider)? + one + fraction)
+    }
+
+    /// Split this [Epoch] into a UTC calendar date and time, using an
+    /// Earth orientation [Provider].
+    ///
+    /// This returns `(year, month, day, hour, minute, second,
+    /// nanos)`. Inside an inserted leap second, `second` is 60. This
+    /// is the inverse of
+    /// [from_utc_gregorian_leap][Self::from_utc_gregorian_leap].
+    ///
+    /// Returns [None] if the provider has no data for this epoch.
+    pub fn to_utc_gregorian_leap<P>(
+        &self,
+        provider: &P,
+    ) -> Option<(i32, u32, u32, u32, u32, u32, u32)>
+    where
+        P: Provider,
+    {
+        use chrono::{Datelike, Timelike};
+
+        let utc = self.to_utc_with(provider)?;
+        let back = utc.to_tai_with(provider)?;
+
+        // during a leap second, UTC has already moved on to the next
+        // minute, which maps back to one second later in TAI
+        let (name, leap) = if back == *self {
+            (utc.to_name(), 0)
+        } else if back - *self == TimeDelta::new(1, 0)? {
+            (utc.to_name() - chrono::TimeDelta::seconds(1), 1)
+        } else {
+            return None;
+        };
+
+        Some((
+            name.year(),
+            name.month(),
+            name.day(),
+            name.hour(),
+            name.minute(),
+            name.second() + leap,
+            name.nanosecond(),
+        ))
+    }
 }
 
 impl Epoch<UTC> {
@@ -112,31 +573,339 @@ impl Epoch<UTC> {
         chrono::Utc.from_utc_datetime(&self.to_name())
     }
 
+    /// Create an [Epoch] from a chrono UTC timestamp.
+    ///
+    /// This is [from_chrono][Self::from_chrono], taking the timestamp
+    /// by value.
+    pub const fn from_chrono_utc(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Self::from_chrono(&datetime)
+    }
+
+    /// Convert into a chrono UTC timestamp.
+    ///
+    /// This is [to_chrono][Self::to_chrono].
+    pub fn to_chrono_utc(&self) -> chrono::DateTime<chrono::Utc> {
+        self.to_chrono()
+    }
+
+    /// Create an [Epoch] from a chrono timestamp in any time zone,
+    /// such as [chrono::FixedOffset].
+    ///
+    /// The timestamp is normalized to UTC first, so the same instant
+    /// gives the same [Epoch] regardless of its offset.
+    pub fn from_chrono_offset<Tz>(datetime: chrono::DateTime<Tz>) -> Self
+    where
+        Tz: chrono::TimeZone,
+    {
+        Self::from_chrono(&datetime)
+    }
+
+    #[cfg(feature = "std")]
     pub fn now() -> Self {
         Self::from_chrono(&chrono::Utc::now())
     }
+
+    /// TAI - UTC at this epoch, using an Earth orientation
+    /// [Provider].
+    ///
+    /// This is [Provider::tai_utc_for_utc].
+    pub fn tai_minus_utc<P>(&self, provider: &P) -> Option<TimeDelta<TAI>>
+    where
+        P: Provider,
+    {
+        provider.tai_utc_for_utc(self)
+    }
+
+    /// Create an [Epoch] from Unix time in seconds.
+    ///
+    /// Like POSIX, Unix time here ignores leap seconds: every day is
+    /// exactly 86,400 s, and it tracks UTC civil time.
+    pub fn from_unix_seconds(seconds: f64) -> Self {
+        Self::from_name_delta(&UNIX_EPOCH_0, TimeDelta::from_seconds(seconds))
+    }
+
+    /// Unix time in seconds.
+    ///
+    /// See [from_unix_seconds][Self::from_unix_seconds].
+    pub fn to_unix_seconds(&self) -> f64 {
+        self.to_name_delta(&UNIX_EPOCH_0).to_seconds()
+    }
+
+    /// Create an [Epoch] from Unix time in nanoseconds.
+    ///
+    /// See [from_unix_seconds][Self::from_unix_seconds].
+    pub fn from_unix_nanos(nanos: i64) -> Self {
+        let secs = nanos.div_euclid(NANOS_PER_SEC as i64);
+        let nanos = nanos.rem_euclid(NANOS_PER_SEC as i64) as u32;
+        match TimeDelta::new(secs, nanos) {
+            Some(delta) => Self::from_name_delta(&UNIX_EPOCH_0, delta),
+            None => unreachable!("unix nanoseconds out of range"),
+        }
+    }
+
+    /// Unix time in nanoseconds.
+    ///
+    /// Returns [None] if the result does not fit in an [i64], which
+    /// covers roughly the years 1677 to 2262. See
+    /// [from_unix_seconds][Self::from_unix_seconds].
+    pub fn to_unix_nanos(&self) -> Option<i64> {
+        let (secs, nanos) = self.to_name_delta(&UNIX_EPOCH_0).to_raw();
+        secs.checked_mul(NANOS_PER_SEC as i64)?
+            .checked_add(nanos as i64)
+    }
+
+    /// Create an [Epoch] from a [SystemTime][std::time::SystemTime].
+    ///
+    /// This goes through Unix time, so like
+    /// [from_unix_seconds][Self::from_unix_seconds] it is UTC civil
+    /// time and ignores leap seconds. Times before the Unix epoch are
+    /// supported.
+    ///
+    /// Panics if `t` is outside the range of an [Epoch]. See
+    /// [try_from_system_time][Self::try_from_system_time] for a
+    /// version that does not panic.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(t: std::time::SystemTime) -> Self {
+        Self::try_from_system_time(t).expect("system time out of range for Epoch")
+    }
+
+    /// Create an [Epoch] from a [SystemTime][std::time::SystemTime],
+    /// if it fits.
+    ///
+    /// See [from_system_time][Self::from_system_time]. Returns [None]
+    /// if `t` is outside the range of an [Epoch].
+    #[cfg(feature = "std")]
+    pub fn try_from_system_time(t: std::time::SystemTime) -> Option<Self> {
+        let (duration, negative) = match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => (duration, false),
+            Err(e) => (e.duration(), true),
+        };
+
+        let delta: TimeDelta<UTC> = duration.try_into().ok()?;
+        Self::from_name(UNIX_EPOCH_0).checked_add(if negative { -delta } else { delta })
+    }
+
+    /// Convert to a [SystemTime][std::time::SystemTime].
+    ///
+    /// See [from_system_time][Self::from_system_time]. Fails if the
+    /// result is outside the range of a
+    /// [SystemTime][std::time::SystemTime] on this platform.
+    #[cfg(feature = "std")]
+    pub fn to_system_time(&self) -> Result<std::time::SystemTime, super::DurationError> {
+        let delta = self.to_name_delta(&UNIX_EPOCH_0);
+        let time = if delta.is_negative() {
+            std::time::UNIX_EPOCH.checked_sub((-delta).try_into()?)
+        } else {
+            std::time::UNIX_EPOCH.checked_add(delta.try_into()?)
+        };
+
+        time.ok_or(super::DurationError::OutOfRange)
+    }
+
+    /// Midnight UTC on the given proleptic Gregorian date.
+    ///
+    /// Returns [None] if the date is invalid.
+    pub fn from_date(year: i32, month: u32, day: u32) -> Option<Self> {
+        let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+        Some(Self::from_name(date.and_time(chrono::NaiveTime::MIN)))
+    }
+
+    /// Create an [Epoch] from a proleptic Gregorian date and UTC time
+    /// of day.
+    ///
+    /// Returns [None] if the date or time is invalid, such as
+    /// February 30.
+    pub const fn from_gregorian(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+    ) -> Option<Self> {
+        Self::from_gregorian_nanos(year, month, day, hour, min, sec, 0)
+    }
+
+    /// Like [from_gregorian][Self::from_gregorian], with nanoseconds.
+    ///
+    /// Returns [None] if `nano` is not within `[0, 1e9)`.
+    pub const fn from_gregorian_nanos(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+        nano: u32,
+    ) -> Option<Self> {
+        // const fn, so no ? operator
+        let date = match chrono::NaiveDate::from_ymd_opt(year, month, day) {
+            Some(date) => date,
+            None => return None,
+        };
+
+        if nano >= NANOS_PER_SEC {
+            return None;
+        }
+
+        match date.and_hms_nano_opt(hour, min, sec, nano) {
+            Some(datetime) => Some(Self::from_name(datetime)),
+            None => None,
+        }
+    }
+
+    /// Create an [Epoch] from a proleptic Julian calendar date and UTC
+    /// time of day.
+    ///
+    /// This is useful for historical dates, which before 1582 are
+    /// usually given in the Julian calendar. Returns [None] if the
+    /// date or time is invalid.
+    pub const fn from_julian_calendar(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+    ) -> Option<Self> {
+        let date = match julian_to_gregorian(year, month, day) {
+            Some(date) => date,
+            None => return None,
+        };
+
+        match date.and_hms_opt(hour, min, sec) {
+            Some(datetime) => Some(Self::from_name(datetime)),
+            None => None,
+        }
+    }
+
+    /// The proleptic Julian calendar date and UTC time of day of this
+    /// epoch, as year, month, day, hour, minute, and second.
+    ///
+    /// Fractional seconds are truncated. See
+    /// [from_julian_calendar][Self::from_julian_calendar].
+    pub fn to_julian_calendar(&self) -> (i32, u32, u32, u32, u32, u32) {
+        use chrono::Timelike;
+        let name = self.to_name();
+        let (year, month, day) = gregorian_to_julian(name.date());
+        (year, month, day, name.hour(), name.minute(), name.second())
+    }
+
+    /// The UTC date containing this epoch.
+    ///
+    /// This drops the time of day.
+    pub fn to_date(&self) -> chrono::NaiveDate {
+        self.to_name().date()
+    }
+
+    /// Create an [Epoch] from a proleptic Gregorian year, a day of
+    /// that year, and seconds into that day.
+    ///
+    /// January 1 is day 1. Returns [None] if the day is not in that
+    /// year, such as day 366 of a common year, or if `seconds_of_day`
+    /// is not within `[0, 86400)`.
+    pub fn from_year_day(year: i32, doy: u32, seconds_of_day: f64) -> Option<Self> {
+        if !(0.0..SECS_PER_DAY as f64).contains(&seconds_of_day) {
+            return None;
+        }
+
+        let date = chrono::NaiveDate::from_yo_opt(year, doy)?;
+        Some(
+            Self::from_name(date.and_time(chrono::NaiveTime::MIN))
+                + TimeDelta::from_seconds(seconds_of_day),
+        )
+    }
+
+    /// The day of the UTC year containing this epoch, from 1 to 366.
+    pub fn day_of_year(&self) -> u32 {
+        use chrono::Datelike;
+        self.to_date().ordinal()
+    }
+
+    /// Create an [Epoch] from an ISO 8601 week date and seconds into
+    /// that day.
+    ///
+    /// `year` is the ISO week-numbering year, which can differ from
+    /// the calendar year near January 1. Returns [None] if the week
+    /// is not in that year, or if `seconds_of_day` is not within
+    /// `[0, 86400)`.
+    pub fn from_iso_week(
+        year: i32,
+        week: u32,
+        weekday: chrono::Weekday,
+        seconds_of_day: f64,
+    ) -> Option<Self> {
+        if !(0.0..SECS_PER_DAY as f64).contains(&seconds_of_day) {
+            return None;
+        }
+
+        let date = chrono::NaiveDate::from_isoywd_opt(year, week, weekday)?;
+        Some(
+            Self::from_name(date.and_time(chrono::NaiveTime::MIN))
+                + TimeDelta::from_seconds(seconds_of_day),
+        )
+    }
+
+    /// The ISO 8601 week date of the UTC day containing this epoch,
+    /// as the week-numbering year, week, and weekday.
+    ///
+    /// The week-numbering year can differ from the calendar year near
+    /// January 1. For example, January 1, 2021 is in week 53 of 2020.
+    pub fn iso_week(&self) -> (i32, u32, chrono::Weekday) {
+        use chrono::Datelike;
+        let date = self.to_date();
+        let week = date.iso_week();
+        (week.year(), week.week(), date.weekday())
+    }
 }
 
-impl<S> std::clone::Clone for Epoch<S> {
+impl<S> core::clone::Clone for Epoch<S> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<S> std::marker::Copy for Epoch<S> {}
+impl<S> core::marker::Copy for Epoch<S> {}
 
-impl<S> std::fmt::Debug for Epoch<S> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_struct("Epoch").field("delta", &self.delta).finish()
+impl<S> core::fmt::Debug for Epoch<S>
+where
+    S: Scale,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut debug = f.debug_struct("Epoch");
+        // out of range for chrono, the raw delta will have to do
+        if let Some(name) = self.try_to_name() {
+            debug.field("name", &name.to_string());
+        }
+        debug
+            .field("scale", &S::NAME)
+            .field("delta", &self.delta)
+            .finish()
     }
 }
 
-impl<S> std::fmt::Display for Epoch<S>
+/// The alternate form `{:#}` uses
+/// [to_iso8601_with_scale][Epoch::to_iso8601_with_scale].
+///
+/// Epochs outside of chrono's range, more than about 262,000 years
+/// from now, cannot be named. These are shown as a fractional Julian
+/// Day instead, such as `JD 11576489094.574074 TAI`.
+impl<S> core::fmt::Display for Epoch<S>
 where
     S: Scale,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_fmt(format_args!("{} {}", self.to_name(), S::NAME))
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.try_to_name() {
+            Some(_) if f.alternate() => f.write_str(&self.to_iso8601_with_scale()),
+            Some(name) => f.write_fmt(format_args!("{} {}", name, S::NAME)),
+            // out of range for chrono, and maybe for jd() too, so
+            // add up the Julian Day in floating point
+            None => {
+                let offset = (FRAMESHIFT_0 - JULIAN_DAY_0).as_seconds_f64();
+                let jd = (self.delta.to_seconds() + offset) / SECS_PER_DAY as f64;
+                f.write_fmt(format_args!("JD {} {}", jd, S::NAME))
+            }
+        }
     }
 }
 
@@ -146,7 +915,7 @@ impl<S> Default for Epoch<S> {
     }
 }
 
-impl<Tz> std::convert::From<chrono::DateTime<Tz>> for Epoch<UTC>
+impl<Tz> core::convert::From<chrono::DateTime<Tz>> for Epoch<UTC>
 where
     Tz: chrono::TimeZone,
 {
@@ -155,42 +924,42 @@ where
     }
 }
 
-impl std::convert::From<Epoch<UTC>> for chrono::DateTime<chrono::Utc> {
+impl core::convert::From<Epoch<UTC>> for chrono::DateTime<chrono::Utc> {
     fn from(value: Epoch<UTC>) -> Self {
         value.to_chrono()
     }
 }
 
-impl<S> std::cmp::PartialEq for Epoch<S> {
+impl<S> core::cmp::PartialEq for Epoch<S> {
     fn eq(&self, other: &Self) -> bool {
         self.delta.eq(&other.delta)
     }
 }
 
-impl<S> std::cmp::Eq for Epoch<S> {}
+impl<S> core::cmp::Eq for Epoch<S> {}
 
-impl<S> std::cmp::PartialOrd for Epoch<S> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl<S> core::cmp::PartialOrd for Epoch<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.delta.partial_cmp(&other.delta)
     }
 }
 
-impl<S> std::cmp::Ord for Epoch<S> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+impl<S> core::cmp::Ord for Epoch<S> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.delta.cmp(&other.delta)
     }
 }
 
-impl<S> std::hash::Hash for Epoch<S> {
+impl<S> core::hash::Hash for Epoch<S> {
     fn hash<H>(&self, state: &mut H)
     where
-        H: std::hash::Hasher,
+        H: core::hash::Hasher,
     {
         self.delta.hash(state)
     }
 }
 
-impl<S> std::ops::Add<TimeDelta<S>> for Epoch<S> {
+impl<S> core::ops::Add<TimeDelta<S>> for Epoch<S> {
     type Output = Self;
 
     fn add(self, rhs: TimeDelta<S>) -> Self::Output {
@@ -198,13 +967,13 @@ impl<S> std::ops::Add<TimeDelta<S>> for Epoch<S> {
     }
 }
 
-impl<S> std::ops::AddAssign<TimeDelta<S>> for Epoch<S> {
+impl<S> core::ops::AddAssign<TimeDelta<S>> for Epoch<S> {
     fn add_assign(&mut self, rhs: TimeDelta<S>) {
         self.delta += rhs;
     }
 }
 
-impl<S> std::ops::Sub<TimeDelta<S>> for Epoch<S> {
+impl<S> core::ops::Sub<TimeDelta<S>> for Epoch<S> {
     type Output = Self;
 
     fn sub(self, rhs: TimeDelta<S>) -> Self::Output {
@@ -212,16 +981,789 @@ impl<S> std::ops::Sub<TimeDelta<S>> for Epoch<S> {
     }
 }
 
-impl<S> std::ops::SubAssign<TimeDelta<S>> for Epoch<S> {
+impl<S> core::ops::SubAssign<TimeDelta<S>> for Epoch<S> {
     fn sub_assign(&mut self, rhs: TimeDelta<S>) {
         self.delta -= rhs;
     }
 }
 
-impl<S> std::ops::Sub for Epoch<S> {
+impl<S> core::ops::Add<chrono::TimeDelta> for Epoch<S> {
+    type Output = Self;
+
+    fn add(self, rhs: chrono::TimeDelta) -> Self::Output {
+        self + TimeDelta::from_chrono(rhs)
+    }
+}
+
+impl<S> core::ops::AddAssign<chrono::TimeDelta> for Epoch<S> {
+    fn add_assign(&mut self, rhs: chrono::TimeDelta) {
+        *self += TimeDelta::from_chrono(rhs);
+    }
+}
+
+impl<S> core::ops::Sub<chrono::TimeDelta> for Epoch<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: chrono::TimeDelta) -> Self::Output {
+        self - TimeDelta::from_chrono(rhs)
+    }
+}
+
+impl<S> core::ops::SubAssign<chrono::TimeDelta> for Epoch<S> {
+    fn sub_assign(&mut self, rhs: chrono::TimeDelta) {
+        *self -= TimeDelta::from_chrono(rhs);
+    }
+}
+
+impl<S> core::ops::Sub for Epoch<S> {
     type Output = TimeDelta<S>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         self.delta - rhs.delta
     }
 }
+
+/// An iterator over evenly spaced [Epoch]s.
+///
+/// See [Epoch::range].
+pub struct EpochRange<S> {
+    next: Option<Epoch<S>>,
+    end: Epoch<S>,
+    step: TimeDelta<S>,
+}
+
+impl<S> core::clone::Clone for EpochRange<S> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            end: self.end,
+            step: self.step,
+        }
+    }
+}
+
+impl<S> core::fmt::Debug for EpochRange<S>
+where
+    S: Scale,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("EpochRange")
+            .field("next", &self.next)
+            .field("end", &self.end)
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+impl<S> Iterator for EpochRange<S> {
+    type Item = Epoch<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        let past_end = if self.step.is_negative() {
+            current < self.end
+        } else {
+            current > self.end
+        };
+
+        if past_end {
+            return None;
+        }
+
+        // stop instead of overflowing
+        self.next = current.checked_add(self.step);
+        Some(current)
+    }
+}
+
+impl<S> core::iter::FusedIterator for EpochRange<S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    fn leap_2016() -> crate::provider::CelestrakProvider {
+        crate::provider::CelestrakProvider::from_rows(&[
+            (57_752.0, 36, 0.0),
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_755.0, 37, 0.0),
+        ])
+    }
+
+    #[test]
+    fn utc_gregorian_leap_needs_data() {
+        let provider = crate::provider::EmptyProvider;
+        let leap = Epoch::<TAI>::from_utc_gregorian_leap(2016, 12, 31, 23, 59, 60, 0, &provider);
+        assert_eq!(leap, None);
+        assert_eq!(
+            Epoch::<TAI>::default().to_utc_gregorian_leap(&provider),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn utc_gregorian_leap_second() {
+        let provider = leap_2016();
+        let at = |day, hour, minute, second, nanos| {
+            let (year, month) = if day == 1 { (2017, 1) } else { (2016, 12) };
+            Epoch::<TAI>::from_utc_gregorian_leap(
+                year, month, day, hour, minute, second, nanos, &provider,
+            )
+        };
+        let half = TimeDelta::new(0, 500_000_000).unwrap();
+
+        let before = at(31, 23, 59, 59, 0).unwrap();
+        let leap = at(31, 23, 59, 60, 500_000_000).unwrap();
+        let after = at(1, 0, 0, 0, 0).unwrap();
+        assert_eq!(leap - before, TimeDelta::new(1, 500_000_000).unwrap());
+        assert_eq!(after - leap, half);
+
+        // 36 s before the leap, 37 s after
+        let utc = Epoch::<UTC>::from_gregorian(2017, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(after, utc.transmute() + TimeDelta::new(37, 0).unwrap());
+
+        // no leap second at the end of the day before
+        assert_eq!(at(30, 23, 59, 60, 0), None);
+        assert_eq!(at(31, 23, 58, 60, 0), None);
+        assert_eq!(at(31, 23, 59, 59, 1_000_000_000), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_utc_gregorian_leap_second() {
+        let provider = leap_2016();
+        for (fields, seconds) in [
+            ((2016, 12, 31, 23, 59, 59, 0), 0),
+            ((2016, 12, 31, 23, 59, 60, 0), 1),
+            ((2016, 12, 31, 23, 59, 60, 999_999_999), 1),
+            ((2017, 1, 1, 0, 0, 0, 0), 2),
+        ] {
+            let (year, month, day, hour, minute, second, nanos) = fields;
+            let epoch = Epoch::<TAI>::from_utc_gregorian_leap(
+                year, month, day, hour, minute, second, nanos, &provider,
+            )
+            .unwrap();
+            assert_eq!(epoch.to_utc_gregorian_leap(&provider), Some(fields));
+
+            let start = Epoch::<UTC>::from_gregorian(2016, 12, 31, 23, 59, 59).unwrap();
+            let start = start.to_tai_with(&provider).unwrap();
+            assert_eq!((epoch - start).to_raw().0, seconds);
+        }
+    }
+
+    fn seconds(secs: i64) -> TimeDelta<TAI> {
+        TimeDelta::new(secs, 0).unwrap()
+    }
+
+    fn offsets(range: EpochRange<TAI>) -> alloc::vec::Vec<i64> {
+        range.map(|e| (e - Epoch::default()).to_raw().0).collect()
+    }
+
+    #[test]
+    fn range_ascending() {
+        let start = Epoch::<TAI>::default();
+        let range = Epoch::range(start, start + seconds(10), seconds(3));
+        assert_eq!(offsets(range), [0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn range_exact_end() {
+        let start = Epoch::<TAI>::default();
+        let range = Epoch::range(start, start + seconds(9), seconds(3));
+        assert_eq!(offsets(range), [0, 3, 6, 9]);
+
+        let range = Epoch::range(start, start, seconds(3));
+        assert_eq!(offsets(range), [0]);
+    }
+
+    #[test]
+    fn range_descending() {
+        let start = Epoch::<TAI>::default();
+        let range = Epoch::range(start, start - seconds(7), -seconds(3));
+        assert_eq!(offsets(range), [0, -3, -6]);
+    }
+
+    #[test]
+    fn range_empty() {
+        let start = Epoch::<TAI>::default();
+        let end = start + seconds(10);
+        assert_eq!(Epoch::range(start, end, seconds(0)).count(), 0);
+        assert_eq!(Epoch::range(start, end, -seconds(1)).count(), 0);
+        assert_eq!(Epoch::range(end, start, seconds(1)).count(), 0);
+    }
+
+    #[test]
+    fn debug_shows_name() {
+        let epoch = Epoch::<UTC>::from_gregorian(2020, 1, 1, 0, 0, 0).unwrap();
+        let debug = format!("{:?}", epoch);
+        assert!(debug.starts_with("Epoch { name: \"2020-01-01 00:00:00\", scale: \"UTC\", delta: "));
+
+        // too far out to name
+        let epoch = Epoch::<TAI>::default() + TimeDelta::new(1_000_000_000_000_000, 0).unwrap();
+        let debug = format!("{:?}", epoch);
+        assert!(
+            debug.starts_with("Epoch { scale: \"TAI\", delta: TimeDelta { secs: 1000000000000000,")
+        );
+    }
+
+    #[test]
+    fn snap_to_second_halfway() {
+        let epoch = Epoch::<TAI>::default();
+        let at = |nanos| (epoch + TimeDelta::new(5, nanos).unwrap()).snap_to_second();
+        assert_eq!(at(499_999_999), epoch + seconds(5));
+        assert_eq!(at(500_000_000), epoch + seconds(6));
+        assert_eq!(
+            (epoch + TimeDelta::new(4, 500_000_000).unwrap()).snap_to_second(),
+            epoch + seconds(4)
+        );
+        assert_eq!(epoch.snap_to_second(), epoch.round_to(seconds(1)));
+        assert_eq!(
+            (epoch - TimeDelta::new(0, 400_000_000).unwrap()).snap_to_second(),
+            epoch
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn snap_to_second_at_max() {
+        Epoch::<TAI>::MAX.snap_to_second();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn snap_to_leap_second() {
+        let provider = leap_2016();
+        let leap = |nanos| {
+            Epoch::<TAI>::from_utc_gregorian_leap(2016, 12, 31, 23, 59, 60, nanos, &provider)
+                .unwrap()
+        };
+
+        let snapped = leap(400_000_000).snap_to_second();
+        assert_eq!(
+            snapped.to_utc_gregorian_leap(&provider),
+            Some((2016, 12, 31, 23, 59, 60, 0))
+        );
+        let snapped = leap(600_000_000).snap_to_second();
+        assert_eq!(
+            snapped.to_utc_gregorian_leap(&provider),
+            Some((2017, 1, 1, 0, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn display_out_of_range() {
+        let epoch = Epoch::<TAI>::default() + TimeDelta::new(1_000_000_000_000_000, 0).unwrap();
+        assert_eq!(epoch.try_to_name(), None);
+
+        let display = epoch.to_string();
+        let jd: f64 = display
+            .strip_prefix("JD ")
+            .and_then(|s| s.strip_suffix(" TAI"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((jd - epoch.jd()).abs() < 1e-3);
+
+        // as far out as possible, without panicking
+        for delta in [chrono::TimeDelta::MIN, chrono::TimeDelta::MAX] {
+            let epoch = Epoch::<TAI>::from_frameshift(TimeDelta::from_chrono(delta));
+            assert!(epoch.to_string().starts_with("JD "));
+            assert!(format!("{:#}", epoch).starts_with("JD "));
+        }
+    }
+
+    #[test]
+    fn si_seconds_since() {
+        let origin = Epoch::<TAI>::from_mjd(51_544.0);
+        let later = origin + TimeDelta::new(86_400, 1).unwrap();
+        assert_eq!(later.si_seconds_since(&origin), 86_400.000_000_001);
+        assert_eq!(later.si_nanos_since(&origin), 86_400_000_000_001);
+        assert_eq!(origin.si_nanos_since(&later), -86_400_000_000_001);
+        assert_eq!(origin.si_nanos_since(&origin), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn si_seconds_across_leap_second() {
+        // a UTC day with a leap second is 86,401 SI seconds long
+        let provider = leap_2016();
+        let start = Epoch::<UTC>::from_mjd(57_753.0)
+            .to_tai_with(&provider)
+            .unwrap();
+        let end = Epoch::<UTC>::from_mjd(57_754.0)
+            .to_tai_with(&provider)
+            .unwrap();
+        assert_eq!(end.si_seconds_since(&start), 86_401.0);
+    }
+
+    #[test]
+    fn date_round_trip() {
+        let epoch = Epoch::<UTC>::from_date(2024, 2, 29).unwrap();
+        assert_eq!(epoch, Epoch::from_gregorian(2024, 2, 29, 0, 0, 0).unwrap());
+        assert_eq!(
+            epoch.to_date(),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+
+        // the time of day is dropped
+        let late = epoch + TimeDelta::new(86_399, 999_999_999).unwrap();
+        assert_eq!(late.to_date(), epoch.to_date());
+        assert_eq!(Epoch::<UTC>::from_date(2023, 2, 29), None);
+    }
+
+    #[test]
+    fn julian_centuries_since_j2000() {
+        use crate::time::epoch::J1900;
+
+        assert_eq!(J2000.julian_centuries_since_j2000(), 0.0);
+        assert_eq!(J1900.julian_centuries_since_j2000(), -1.0);
+
+        let later = J2000 + TimeDelta::from_days(36_525.0 / 2.0);
+        assert_eq!(later.julian_centuries_since_j2000(), 0.5);
+        assert_eq!(later.julian_millennia_since_j2000(), 0.05);
+    }
+
+    #[test]
+    fn mjd_day_nanos_round_trip() {
+        let epoch = Epoch::<UTC>::from_gregorian(2024, 2, 29, 12, 0, 0).unwrap()
+            + TimeDelta::new(0, 1).unwrap();
+        assert_eq!(epoch.to_mjd_day_nanos(), (60369, 43_200_000_000_001));
+        assert_eq!(
+            Epoch::from_mjd_day_nanos(60369, 43_200_000_000_001),
+            Some(epoch)
+        );
+
+        // pairs sort in the same order as epochs
+        let before = epoch - TimeDelta::new(0, 2).unwrap();
+        assert!(before.to_mjd_day_nanos() < epoch.to_mjd_day_nanos());
+        let early = Epoch::<UTC>::from_gregorian(1800, 1, 1, 0, 0, 0).unwrap();
+        assert!(early.to_mjd_day_nanos() < before.to_mjd_day_nanos());
+        assert_eq!(
+            Epoch::from_mjd_day_nanos(early.to_mjd_day_nanos().0, early.to_mjd_day_nanos().1),
+            Some(early)
+        );
+    }
+
+    #[test]
+    fn before_1900() {
+        let mjd_0 = Epoch::<UTC>::from_gregorian(1858, 11, 17, 0, 0, 0).unwrap();
+        assert_eq!(mjd_0.mjd(), 0.0);
+
+        // 4714 BCE, proleptic Gregorian
+        let jd_0 = Epoch::<UTC>::from_gregorian(-4713, 11, 24, 12, 0, 0).unwrap();
+        assert_eq!(jd_0.jd(), 0.0);
+        assert_eq!(Epoch::<UTC>::from_jd(0.0), jd_0);
+
+        let bce = Epoch::<UTC>::from_gregorian_nanos(-500, 3, 1, 6, 30, 15, 250).unwrap();
+        assert_eq!(
+            bce.to_name(),
+            chrono::NaiveDate::from_ymd_opt(-500, 3, 1)
+                .unwrap()
+                .and_hms_nano_opt(6, 30, 15, 250)
+                .unwrap()
+        );
+        let (day, nanos) = bce.to_mjd_day_nanos();
+        assert!(day < 0);
+        assert_eq!(Epoch::from_mjd_day_nanos(day, nanos), Some(bce));
+
+        // a hair before 1900 rounds to 1900 rather than panicking
+        let hair = Epoch::<TT>::from_frameshift(TimeDelta::from_seconds(-1e-300));
+        assert_eq!(hair, Epoch::from_frameshift(TimeDelta::new(0, 0).unwrap()));
+    }
+
+    #[test]
+    fn unix_time() {
+        let epoch = Epoch::<UTC>::from_gregorian(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(Epoch::from_unix_seconds(0.0), epoch);
+        assert_eq!(epoch.to_unix_nanos(), Some(0));
+
+        // 2017-01-01, just after a leap second, which Unix time ignores
+        let epoch = Epoch::<UTC>::from_gregorian(2017, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(epoch.to_unix_seconds(), 1_483_228_800.0);
+        assert_eq!(Epoch::from_unix_seconds(1_483_228_800.0), epoch);
+
+        let before = Epoch::<UTC>::from_unix_nanos(-1);
+        assert_eq!(
+            before,
+            Epoch::from_gregorian_nanos(1969, 12, 31, 23, 59, 59, 999_999_999).unwrap()
+        );
+        assert_eq!(before.to_unix_nanos(), Some(-1));
+
+        let far = Epoch::<UTC>::from_gregorian(2300, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(far.to_unix_nanos(), None);
+    }
+
+    #[test]
+    fn besselian_year() {
+        use crate::time::epoch::B1950;
+
+        assert_eq!(Epoch::from_besselian_year(1950.0), B1950);
+        assert_eq!(B1950.to_besselian_year(), 1950.0);
+        assert!((B1950.jd() - 2_433_282.423_459_05).abs() < 1e-8);
+
+        let b1900 = Epoch::from_besselian_year(1900.0);
+        assert!((b1900.jd() - 2_415_020.313_52).abs() < 1e-6);
+        assert!((J2000.to_besselian_year() - 2000.0012775).abs() < 1e-6);
+    }
+
+    #[test]
+    fn julian_centuries_since() {
+        use crate::time::epoch::J1900;
+
+        assert_eq!(J2000.julian_centuries_since(&J1900), 1.0);
+        assert_eq!(J1900.julian_centuries_since(&J2000), -1.0);
+        assert_eq!(J2000.julian_centuries_since(&J2000), 0.0);
+
+        // one nanosecond still registers
+        let later = J2000 + TimeDelta::new(0, 1).unwrap();
+        assert!(later.julian_centuries_since(&J2000) > 0.0);
+    }
+
+    #[test]
+    fn rfc3339_format() {
+        assert_eq!(J2000.to_rfc3339(), "2000-01-01T12:00:00.000000000");
+        assert_eq!(
+            J2000.to_iso8601_with_scale(),
+            "2000-01-01T12:00:00.000000000[TT]"
+        );
+        assert_eq!(format!("{J2000}"), "2000-01-01 12:00:00 TT");
+        assert_eq!(format!("{J2000:#}"), "2000-01-01T12:00:00.000000000[TT]");
+
+        let epoch = J2000 + TimeDelta::new(0, 1).unwrap();
+        assert_eq!(epoch.to_iso8601_with_scale().parse(), Ok(epoch));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn system_time() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let t = UNIX_EPOCH + Duration::new(1_483_228_800, 250);
+        let epoch = Epoch::from_system_time(t);
+        assert_eq!(
+            epoch,
+            Epoch::from_gregorian_nanos(2017, 1, 1, 0, 0, 0, 250).unwrap()
+        );
+        assert_eq!(epoch.to_system_time(), Ok(t));
+
+        // before the Unix epoch
+        let t = UNIX_EPOCH - Duration::new(86_400, 0);
+        let epoch = Epoch::from_system_time(t);
+        assert_eq!(epoch, Epoch::from_date(1969, 12, 31).unwrap());
+        assert_eq!(epoch.to_system_time(), Ok(t));
+
+        let now = SystemTime::now();
+        assert_eq!(Epoch::from_system_time(now).to_system_time(), Ok(now));
+        assert_eq!(
+            Epoch::try_from_system_time(now),
+            Some(Epoch::from_system_time(now))
+        );
+
+        // as far in the future as this platform allows
+        if let Some(far) = UNIX_EPOCH.checked_add(Duration::from_secs(i64::MAX as u64)) {
+            assert_eq!(Epoch::try_from_system_time(far), None);
+        }
+    }
+
+    #[test]
+    fn round_and_truncate() {
+        let minute = TimeDelta::<UTC>::new(60, 0).unwrap();
+        let at = |h, m, s| Epoch::<UTC>::from_gregorian(2024, 6, 1, h, m, s).unwrap();
+
+        assert_eq!(at(12, 34, 29).round_to(minute), at(12, 34, 0));
+        assert_eq!(at(12, 34, 31).round_to(minute), at(12, 35, 0));
+        assert_eq!(at(12, 34, 59).truncate_to(minute), at(12, 34, 0));
+
+        // halfway rounds to the even multiple
+        assert_eq!(at(12, 34, 30).round_to(minute), at(12, 34, 0));
+        assert_eq!(at(12, 35, 30).round_to(minute), at(12, 36, 0));
+
+        // sign is ignored, and zero is a no-op
+        assert_eq!(at(12, 34, 31).round_to(-minute), at(12, 35, 0));
+        let zero = TimeDelta::new(0, 0).unwrap();
+        assert_eq!(at(12, 34, 31).truncate_to(zero), at(12, 34, 31));
+
+        // before FRAMESHIFT_0, truncation still goes into the past
+        let early = Epoch::<UTC>::from_gregorian(1899, 12, 31, 23, 59, 59).unwrap();
+        let early = early + TimeDelta::new(0, 500_000_000).unwrap();
+        let second = TimeDelta::new(1, 0).unwrap();
+        assert_eq!(
+            early.truncate_to(second),
+            Epoch::from_gregorian(1899, 12, 31, 23, 59, 59).unwrap()
+        );
+        assert_eq!(
+            early.round_to(second),
+            Epoch::from_gregorian(1900, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn tai_minus_utc() {
+        let provider = leap_2016();
+        let before = Epoch::<UTC>::from_gregorian(2016, 12, 31, 23, 59, 59).unwrap();
+        let after = Epoch::<UTC>::from_gregorian(2017, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(before.tai_minus_utc(&provider), TimeDelta::new(36, 0));
+        assert_eq!(after.tai_minus_utc(&provider), TimeDelta::new(37, 0));
+
+        // the same instants, measured in TAI
+        let before = before.to_tai_with(&provider).unwrap();
+        let after = after.to_tai_with(&provider).unwrap();
+        assert_eq!(before.tai_minus_utc(&provider), TimeDelta::new(36, 0));
+        assert_eq!(after.tai_minus_utc(&provider), TimeDelta::new(37, 0));
+
+        let empty = crate::provider::EmptyProvider;
+        assert_eq!(before.tai_minus_utc(&empty), None);
+    }
+
+    #[test]
+    fn diff_with_stateless() {
+        let tai = J2000.to_tai();
+        assert_eq!(
+            J2000.diff_with(&tai, &crate::provider::EmptyProvider),
+            TimeDelta::new(0, 0)
+        );
+        let utc = Epoch::<UTC>::from_mjd(51_544.5);
+        assert_eq!(J2000.diff_with(&utc, &crate::provider::EmptyProvider), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diff_with_across_leap_second() {
+        let provider = leap_2016();
+        let before = Epoch::<UTC>::from_gregorian(2016, 12, 31, 23, 59, 0).unwrap();
+        let after =
+            Epoch::<TAI>::from_utc_gregorian_leap(2017, 1, 1, 0, 1, 0, 0, &provider).unwrap();
+
+        // two minutes of UTC, plus the leap second
+        assert_eq!(after.diff_with(&before, &provider), TimeDelta::new(121, 0));
+
+        // but only two minutes measured in UTC
+        assert_eq!(before.diff_with(&after, &provider), TimeDelta::new(-120, 0));
+    }
+
+    #[test]
+    fn year_day() {
+        let epoch = Epoch::<UTC>::from_year_day(2024, 366, 43_200.5).unwrap();
+        assert_eq!(
+            epoch,
+            Epoch::from_gregorian_nanos(2024, 12, 31, 12, 0, 0, 500_000_000).unwrap()
+        );
+        assert_eq!(epoch.day_of_year(), 366);
+        assert_eq!(
+            Epoch::<UTC>::from_date(2023, 3, 1).unwrap().day_of_year(),
+            60
+        );
+
+        assert_eq!(Epoch::<UTC>::from_year_day(2023, 366, 0.0), None);
+        assert_eq!(Epoch::<UTC>::from_year_day(2024, 1, 86_400.0), None);
+        assert_eq!(Epoch::<UTC>::from_year_day(2024, 1, -1.0), None);
+    }
+
+    #[test]
+    fn iso_week() {
+        use chrono::Weekday;
+
+        // 2021-01-03 is a Sunday in week 53 of 2020
+        let epoch = Epoch::<UTC>::from_date(2021, 1, 3).unwrap();
+        assert_eq!(epoch.iso_week(), (2020, 53, Weekday::Sun));
+        assert_eq!(
+            Epoch::from_iso_week(2020, 53, Weekday::Sun, 0.0),
+            Some(epoch)
+        );
+
+        // week 1 of 2025 starts on 2024-12-30
+        let epoch = Epoch::<UTC>::from_iso_week(2025, 1, Weekday::Mon, 3_600.0).unwrap();
+        assert_eq!(epoch, Epoch::from_gregorian(2024, 12, 30, 1, 0, 0).unwrap());
+        assert_eq!(epoch.iso_week(), (2025, 1, Weekday::Mon));
+
+        assert_eq!(
+            Epoch::<UTC>::from_iso_week(2021, 53, Weekday::Mon, 0.0),
+            None
+        );
+        assert_eq!(
+            Epoch::<UTC>::from_iso_week(2021, 1, Weekday::Mon, 86_400.0),
+            None
+        );
+    }
+
+    #[test]
+    fn chrono_by_value() {
+        use chrono::TimeZone;
+
+        let utc = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let epoch = Epoch::from_chrono_utc(utc);
+        assert_eq!(epoch, Epoch::from_gregorian(2024, 6, 1, 12, 0, 0).unwrap());
+        assert_eq!(epoch.to_chrono_utc(), utc);
+
+        // the same instant at any offset
+        let offset = chrono::FixedOffset::east_opt(5 * 3_600 + 1_800).unwrap();
+        let local = offset.with_ymd_and_hms(2024, 6, 1, 17, 30, 0).unwrap();
+        assert_eq!(Epoch::from_chrono_offset(local), epoch);
+    }
+
+    #[test]
+    fn min_max_sentinels() {
+        assert!(Epoch::<TT>::MIN < J2000 && J2000 < Epoch::MAX);
+        assert_eq!(J2000.clamp(Epoch::MIN, Epoch::MAX), J2000);
+        assert_eq!(Epoch::MAX.min(J2000), J2000);
+        assert_eq!(Epoch::<TT>::MAX.try_to_name(), None);
+        assert_eq!(
+            Epoch::<TT>::MIN.checked_sub(TimeDelta::new(1, 0).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn convert_to_scale() {
+        let tt = J2000.convert_to::<TAI>().convert_to::<TT>();
+        assert_eq!(tt, J2000);
+        assert_eq!(J2000.convert_to::<GPS>(), J2000.to_gps(),);
+        assert_eq!(
+            J2000.convert::<TAI, _>(&crate::provider::EmptyProvider),
+            Some(J2000.to_tai())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_with_provider() {
+        let provider = leap_2016();
+        let utc = Epoch::<UTC>::from_gregorian(2017, 1, 1, 0, 0, 0).unwrap();
+        let tai = utc.convert::<TAI, _>(&provider).unwrap();
+        assert_eq!(tai.convert::<UTC, _>(&provider), Some(utc));
+        assert_eq!(Some(tai), utc.to_tai_with(&provider));
+    }
+
+    #[test]
+    fn fractional_jd_and_mjd() {
+        assert_eq!(J2000.jd(), 2_451_545.0);
+        assert_eq!(J2000.mjd(), 51_544.5);
+        assert_eq!(Epoch::<TT>::from_jd(2_451_545.0), J2000);
+        assert_eq!(Epoch::<TT>::from_mjd(51_544.5), J2000);
+
+        // the two differ by exactly 2400000.5 days
+        let epoch = Epoch::<TT>::from_mjd(60_000.25);
+        assert_eq!(epoch.jd() - epoch.mjd(), 2_400_000.5);
+        assert_eq!(Epoch::from_jd(epoch.jd()), epoch);
+    }
+
+    #[test]
+    fn jd_two_part() {
+        assert_eq!(J2000.jd_two_part(), (2_451_545.0, 0.0));
+
+        // nanosecond precision survives the round trip
+        let epoch = J2000 + TimeDelta::new(3_600, 1).unwrap();
+        let (jd1, jd2) = epoch.jd_two_part();
+        assert_eq!(jd1, 2_451_545.0);
+        assert!((0.0..1.0).contains(&jd2));
+        assert_eq!(Epoch::from_jd_two_part(jd1, jd2), Some(epoch));
+
+        // any split of the same date works
+        assert_eq!(
+            Epoch::<TT>::from_jd_two_part(2_400_000.5, 51_544.5),
+            Some(J2000)
+        );
+        assert_eq!(
+            Epoch::<TT>::from_jd_two_part(2_451_545.5, -0.5),
+            Some(J2000)
+        );
+        assert_eq!(Epoch::<TT>::from_jd_two_part(f64::NAN, 0.0), None);
+        assert_eq!(Epoch::<TT>::from_jd_two_part(1e300, 0.0), None);
+    }
+
+    #[test]
+    fn chrono_delta_ops() {
+        let hour = chrono::TimeDelta::hours(1);
+        let later = J2000 + hour;
+        assert_eq!(later, J2000 + TimeDelta::new(3_600, 0).unwrap());
+        assert_eq!(later - hour, J2000);
+
+        let mut epoch = J2000;
+        epoch += chrono::TimeDelta::nanoseconds(-1);
+        assert_eq!(epoch, J2000 - TimeDelta::new(0, 1).unwrap());
+        epoch -= chrono::TimeDelta::nanoseconds(-1);
+        assert_eq!(epoch, J2000);
+    }
+
+    #[test]
+    fn cmp_instant() {
+        use core::cmp::Ordering;
+
+        let provider = crate::provider::EmptyProvider;
+        let tai = J2000.to_tai();
+        assert_eq!(J2000.cmp_instant(&tai, &provider), Some(Ordering::Equal));
+
+        // the same name is 32.184 s later in TAI than in TT
+        let named = J2000.transmute::<TAI>();
+        assert_eq!(J2000.cmp_instant(&named, &provider), Some(Ordering::Less));
+        assert_eq!(
+            named.cmp_instant(&J2000, &provider),
+            Some(Ordering::Greater)
+        );
+
+        // UTC needs data
+        let utc = J2000.transmute::<UTC>();
+        assert_eq!(J2000.cmp_instant(&utc, &provider), None);
+    }
+
+    #[test]
+    fn from_gregorian_validates() {
+        let epoch = Epoch::<UTC>::from_gregorian_nanos(2024, 2, 29, 23, 59, 59, 999_999_999);
+        assert_eq!(
+            epoch.map(|e| e.to_name()),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29).and_then(|d| d.and_hms_nano_opt(
+                23,
+                59,
+                59,
+                999_999_999
+            ))
+        );
+
+        assert_eq!(Epoch::<UTC>::from_gregorian(2024, 2, 30, 0, 0, 0), None);
+        assert_eq!(Epoch::<UTC>::from_gregorian(2023, 13, 1, 0, 0, 0), None);
+        assert_eq!(Epoch::<UTC>::from_gregorian(2023, 1, 1, 24, 0, 0), None);
+        assert_eq!(Epoch::<UTC>::from_gregorian(2023, 1, 1, 0, 60, 0), None);
+        assert_eq!(
+            Epoch::<UTC>::from_gregorian_nanos(2023, 1, 1, 0, 0, 0, 1_000_000_000),
+            None
+        );
+
+        // usable in constants
+        const NEW_YEAR: Option<Epoch<UTC>> = Epoch::from_gregorian(2000, 1, 1, 0, 0, 0);
+        assert_eq!(NEW_YEAR, Some(Epoch::from_mjd(51_544.0)));
+    }
+
+    #[test]
+    fn julian_calendar() {
+        // the day after Julian 1582-10-04 was Gregorian 1582-10-15
+        let last = Epoch::<UTC>::from_julian_calendar(1582, 10, 4, 0, 0, 0).unwrap();
+        assert_eq!(
+            last + TimeDelta::from_days(1.0),
+            Epoch::from_gregorian(1582, 10, 15, 0, 0, 0).unwrap()
+        );
+
+        let jd_0 = Epoch::<UTC>::from_julian_calendar(-4712, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(jd_0.jd(), 0.0);
+
+        // 1900 is a leap year only in the Julian calendar
+        let leap = Epoch::<UTC>::from_julian_calendar(1900, 2, 29, 6, 0, 0).unwrap();
+        assert_eq!(leap, Epoch::from_gregorian(1900, 3, 13, 6, 0, 0).unwrap());
+        assert_eq!(leap.to_julian_calendar(), (1900, 2, 29, 6, 0, 0));
+
+        let epoch = Epoch::<UTC>::from_gregorian(2024, 6, 1, 12, 34, 56).unwrap();
+        assert_eq!(epoch.to_julian_calendar(), (2024, 5, 19, 12, 34, 56));
+
+        assert_eq!(
+            Epoch::<UTC>::from_julian_calendar(2024, 13, 1, 0, 0, 0),
+            None
+        );
+        assert_eq!(
+            Epoch::<UTC>::from_julian_calendar(2024, 1, 1, 25, 0, 0),
+            None
+        );
+    }
+}
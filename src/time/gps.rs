@@ -0,0 +1,247 @@
+use super::name::GPS_WEEK_0;
+use super::{Epoch, TimeDelta, GPS, NANOS_PER_SEC, SECS_PER_WEEK};
+use crate::provider::Provider;
+
+/// Legacy GPS week numbers roll over every 1,024 weeks.
+pub const GPS_WEEK_ROLLOVER: u32 = 1_024;
+
+/// An error encountered while validating a GPS week and time of week.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum GpsWeekError {
+    #[error("GPS week {0} is out of range")]
+    BadWeek(i64),
+    #[error("time of week {0} s is not within a single week")]
+    BadTimeOfWeek(f64),
+    #[error("no UTC data is available for GPS week {0}")]
+    MissingData(i64),
+}
+
+impl Epoch<GPS> {
+    /// Create an [Epoch] from a full GPS week number and seconds into
+    /// that week.
+    ///
+    /// The week number must not be truncated to 10 or 13 bits. Use
+    /// [from_week_seconds_with][Self::from_week_seconds_with] to
+    /// validate the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is out of range, or if `seconds` is not
+    /// finite.
+    pub fn from_week_seconds(week: i64, seconds: f64) -> Self {
+        match Self::checked_from_week_seconds(week, seconds) {
+            Some(epoch) => epoch,
+            None => panic!("GPS week out of range"),
+        }
+    }
+
+    // from_week_seconds, but None instead of panicking
+    fn checked_from_week_seconds(week: i64, seconds: f64) -> Option<Self> {
+        let weeks = TimeDelta::new(week.checked_mul(SECS_PER_WEEK as i64)?, 0)?;
+        let delta = weeks.checked_add(TimeDelta::try_from_seconds(seconds)?)?;
+        Self::from_name(GPS_WEEK_0).checked_add(delta)
+    }
+
+    /// Split this [Epoch] into a full GPS week number and seconds into
+    /// that week.
+    ///
+    /// The seconds are always in `[0, 604_800)`. This is the inverse
+    /// of [from_week_seconds][Self::from_week_seconds], and unlike
+    /// [to_week_tow][Self::to_week_tow] it also works before the GPS
+    /// epoch, where the week is negative.
+    pub fn to_week_seconds(&self) -> (i64, f64) {
+        let (secs, nanos) = self.to_name_delta(&GPS_WEEK_0).to_raw();
+        let week = secs.div_euclid(SECS_PER_WEEK as i64);
+        let secs = secs.rem_euclid(SECS_PER_WEEK as i64);
+        (week, secs as f64 + nanos as f64 / NANOS_PER_SEC as f64)
+    }
+
+    /// Create an [Epoch] from a GPS week number and time of week, in
+    /// seconds, counted from the GPS epoch at 1980-01-06T00:00:00.
+    ///
+    /// The week number must not be truncated to 10 or 13 bits. See
+    /// [from_week_tow_rollover][Self::from_week_tow_rollover] for
+    /// those.
+    pub fn from_week_tow(week: u32, tow: f64) -> Self {
+        Self::from_week_seconds(week as i64, tow)
+    }
+
+    /// Split this [Epoch] into a GPS week number and time of week, in
+    /// seconds.
+    ///
+    /// The time of week is always in `[0, 604_800)`. This is the
+    /// inverse of [from_week_tow][Self::from_week_tow].
+    ///
+    /// # Panics
+    ///
+    /// Panics if this epoch is before the GPS epoch, or more than
+    /// [u32::MAX] weeks after it. Use
+    /// [to_week_seconds][Self::to_week_seconds] for those.
+    pub fn to_week_tow(&self) -> (u32, f64) {
+        let (week, tow) = self.to_week_seconds();
+        match u32::try_from(week) {
+            Ok(week) => (week, tow),
+            Err(_) => panic!("GPS week out of range"),
+        }
+    }
+
+    /// Create an [Epoch] from a GPS week number that has rolled over,
+    /// and time of week in seconds.
+    ///
+    /// Legacy GPS navigation messages only carry the week number
+    /// modulo [GPS_WEEK_ROLLOVER]. This picks the first full week
+    /// number on or after `base_week` that matches `week`.
+    pub fn from_week_tow_rollover(week: u32, tow: f64, base_week: u32) -> Self {
+        let rollover = GPS_WEEK_ROLLOVER as i64;
+        let offset = (week as i64 - base_week as i64).rem_euclid(rollover);
+        Self::from_week_seconds(base_week as i64 + offset, tow)
+    }
+
+    /// Create an [Epoch] from a full GPS week number and seconds into
+    /// that week, and validate it against an Earth orientation
+    /// [Provider].
+    ///
+    /// The week must not be before the GPS epoch, and the seconds must
+    /// be within `[0, 604_800)`. The result must also convert to UTC,
+    /// which fails if the provider has no data for that week. This
+    /// catches many week rollover mistakes, which tend to produce
+    /// dates far from any real data.
+    ///
+    /// Times during a leap second are accepted, even though UTC
+    /// cannot name them exactly.
+    pub fn from_week_seconds_with<P>(
+        week: i64,
+        seconds: f64,
+        provider: &P,
+    ) -> Result<Self, GpsWeekError>
+    where
+        P: Provider,
+    {
+        if week < 0 {
+            return Err(GpsWeekError::BadWeek(week));
+        }
+
+        if !(0.0..SECS_PER_WEEK as f64).contains(&seconds) {
+            return Err(GpsWeekError::BadTimeOfWeek(seconds));
+        }
+
+        let epoch =
+            Self::checked_from_week_seconds(week, seconds).ok_or(GpsWeekError::BadWeek(week))?;
+        epoch
+            .to_utc_with(provider)
+            .ok_or(GpsWeekError::MissingData(week))?;
+
+        Ok(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn week_of_2020() {
+        // 2020-01-01 was a Wednesday
+        let epoch = Epoch::<GPS>::from_mjd(58_849.0);
+        assert_eq!(epoch.to_week_tow(), (2_086, 259_200.0));
+        assert_eq!(Epoch::from_week_tow(2_086, 259_200.0), epoch);
+    }
+
+    #[test]
+    fn week_tow_round_trip() {
+        for &(week, tow) in &[(0, 0.0), (1_024, 0.25), (2_086, 604_799.5), (2_300, 12.0)] {
+            let epoch = Epoch::<GPS>::from_week_tow(week, tow);
+            assert_eq!(epoch.to_week_tow(), (week, tow));
+        }
+
+        // a time of week past the end carries into the next week
+        let epoch = Epoch::<GPS>::from_week_tow(2_086, 604_800.0 + 1.0);
+        assert_eq!(epoch.to_week_tow(), (2_087, 1.0));
+    }
+
+    #[test]
+    fn week_before_gps_epoch() {
+        let epoch = Epoch::<GPS>::from_mjd(44_243.0);
+        assert_eq!(epoch.to_week_seconds(), (-1, 518_400.0));
+        assert_eq!(Epoch::from_week_seconds(-1, 518_400.0), epoch);
+    }
+
+    #[test]
+    #[should_panic]
+    fn week_tow_before_gps_epoch() {
+        Epoch::<GPS>::from_mjd(44_243.0).to_week_tow();
+    }
+
+    #[test]
+    fn week_tow_rollover() {
+        let epoch = Epoch::<GPS>::from_week_tow(2_086, 100.0);
+        assert_eq!(Epoch::from_week_tow_rollover(38, 100.0, 2_048), epoch);
+        assert_eq!(Epoch::from_week_tow_rollover(38, 100.0, 2_086), epoch);
+        assert_eq!(
+            Epoch::from_week_tow_rollover(38, 100.0, 1_024),
+            Epoch::from_week_tow(1_062, 100.0)
+        );
+        assert_eq!(
+            Epoch::from_week_tow_rollover(38, 100.0, 2_087),
+            Epoch::from_week_tow(3_110, 100.0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn provider() -> crate::provider::CelestrakProvider {
+        // around the leap second at the end of 2016, and week 2000
+        crate::provider::CelestrakProvider::from_rows(&[
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_755.0, 37, 0.0),
+            (58_243.0, 37, 0.0),
+            (58_245.0, 37, 0.0),
+        ])
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn week_2000_in_utc() {
+        let provider = provider();
+        let epoch = Epoch::<GPS>::from_week_seconds_with(2_000, 0.0, &provider).unwrap();
+        let utc = Epoch::<crate::time::UTC>::from_gregorian(2018, 5, 5, 23, 59, 42).unwrap();
+        assert_eq!(epoch.to_utc_with(&provider), Some(utc));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn week_seconds_during_leap_second() {
+        // 2016-12-31T23:59:60.5 UTC
+        let provider = provider();
+        let epoch = Epoch::<GPS>::from_week_seconds_with(1_930, 17.5, &provider).unwrap();
+        assert_eq!(epoch.to_week_seconds(), (1_930, 17.5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn week_seconds_rejected() {
+        let provider = provider();
+        let check = |week, seconds| Epoch::<GPS>::from_week_seconds_with(week, seconds, &provider);
+
+        assert_eq!(check(-1, 0.0), Err(GpsWeekError::BadWeek(-1)));
+        assert_eq!(check(i64::MAX, 0.0), Err(GpsWeekError::BadWeek(i64::MAX)));
+        assert_eq!(check(1_930, -1.0), Err(GpsWeekError::BadTimeOfWeek(-1.0)));
+        assert_eq!(
+            check(1_930, 604_800.0),
+            Err(GpsWeekError::BadTimeOfWeek(604_800.0))
+        );
+        assert!(matches!(
+            check(1_930, f64::NAN),
+            Err(GpsWeekError::BadTimeOfWeek(_))
+        ));
+
+        // a week off by a rollover lands far from any data
+        assert_eq!(check(906, 17.5), Err(GpsWeekError::MissingData(906)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn week_seconds_overflow() {
+        Epoch::<GPS>::from_week_seconds(i64::MAX / 2, 0.0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Conversions to and from [hifitime].
+//!
+//! hifitime measures every epoch as a duration since January 1, 1900
+//! at 00:00 TAI, the same instant as
+//! [FRAMESHIFT_0][super::name::FRAMESHIFT_0] in [TAI]. So there is no
+//! offset between the two crates: an [Epoch] in [TAI] and a
+//! [hifitime::Epoch] with the same duration since that reference are
+//! the same instant. Convert through [TAI] to reach other scales.
+//!
+//! hifitime durations have a smaller range than [TimeDelta].
+//! Conversions into hifitime saturate at the ends of its range.
+
+use super::{Epoch, TimeDelta, TAI};
+
+impl<S> core::convert::From<::hifitime::Duration> for TimeDelta<S> {
+    fn from(duration: ::hifitime::Duration) -> Self {
+        match Self::from_total_nanos(duration.total_nanoseconds()) {
+            Some(delta) => delta,
+            None => unreachable!("hifitime duration out of range"),
+        }
+    }
+}
+
+impl<S> core::convert::From<TimeDelta<S>> for ::hifitime::Duration {
+    fn from(delta: TimeDelta<S>) -> Self {
+        Self::from_total_nanoseconds(delta.to_total_nanos())
+    }
+}
+
+impl core::convert::From<::hifitime::Epoch> for Epoch<TAI> {
+    fn from(epoch: ::hifitime::Epoch) -> Self {
+        Self::from_frameshift(epoch.duration_since_j1900_tai.into())
+    }
+}
+
+impl core::convert::From<Epoch<TAI>> for ::hifitime::Epoch {
+    fn from(epoch: Epoch<TAI>) -> Self {
+        Self::from_tai_duration(epoch.to_frameshift().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_round_trip() {
+        let delta = TimeDelta::<TAI>::new(-2, 250).unwrap();
+        let duration = ::hifitime::Duration::from(delta);
+        assert_eq!(duration.total_nanoseconds(), -1_999_999_750);
+        assert_eq!(TimeDelta::<TAI>::from(duration), delta);
+    }
+
+    #[test]
+    fn duration_saturates() {
+        // about six million years
+        let delta = TimeDelta::<TAI>::new(200_000_000_000_000, 0).unwrap();
+        assert_eq!(::hifitime::Duration::from(delta), ::hifitime::Duration::MAX);
+        assert_eq!(
+            ::hifitime::Duration::from(-delta),
+            ::hifitime::Duration::MIN
+        );
+    }
+
+    #[test]
+    fn epoch_round_trip() {
+        let theirs = ::hifitime::Epoch::from_gregorian_tai(2000, 1, 1, 12, 0, 0, 250);
+        let ours = Epoch::<TAI>::from(theirs);
+        let expected = chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_nano_opt(12, 0, 0, 250)
+            .unwrap();
+        assert_eq!(ours, Epoch::from_name(expected));
+        assert_eq!(::hifitime::Epoch::from(ours), theirs);
+
+        // the reference instants line up exactly
+        let origin = ::hifitime::Epoch::from_tai_duration(::hifitime::Duration::ZERO);
+        assert_eq!(
+            Epoch::<TAI>::from(origin).to_frameshift(),
+            TimeDelta::new(0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn utc_matches_hifitime() {
+        use crate::time::UTC;
+
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_906.0, 37, 0.0),
+        ]);
+
+        // a minute before and just after the leap second at the end of
+        // 2016
+        for (y, m, d, h, min, s, nanos) in [
+            (2016, 12, 31, 23, 59, 0, 0),
+            (2017, 1, 1, 0, 0, 0, 0),
+            (2017, 6, 1, 12, 34, 56, 789_012_345),
+        ] {
+            let theirs = ::hifitime::Epoch::from_gregorian_utc(
+                y, m as u8, d as u8, h as u8, min as u8, s as u8, nanos,
+            );
+            let ours = Epoch::<UTC>::from_gregorian_nanos(y, m, d, h, min, s, nanos)
+                .unwrap()
+                .to_tai_with(&provider)
+                .unwrap();
+            assert_eq!(ours, Epoch::<TAI>::from(theirs), "{y}-{m}-{d}");
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Epoch, ToScale, ToScaleWith};
+use crate::provider::Provider;
+
+/// Extension methods for iterators over [Epoch]s.
+pub trait EpochIteratorExt<S>: Iterator<Item = Epoch<S>> + Sized {
+    /// Lazily convert each [Epoch] into a new [Scale][super::Scale],
+    /// using an Earth orientation [Provider].
+    ///
+    /// See [ToScaleWith].
+    fn convert_with<T, P>(self, provider: &P) -> ConvertWith<'_, Self, T, P>
+    where
+        Epoch<S>: ToScaleWith<T>,
+        P: Provider,
+    {
+        ConvertWith {
+            iter: self,
+            provider,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Lazily convert each [Epoch] into a new [Scale][super::Scale],
+    /// statelessly.
+    ///
+    /// See [ToScale].
+    fn convert<T>(self) -> Convert<Self, T>
+    where
+        Epoch<S>: ToScale<T>,
+    {
+        Convert {
+            iter: self,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<I, S> EpochIteratorExt<S> for I where I: Iterator<Item = Epoch<S>> {}
+
+/// An iterator that converts [Epoch]s using a [Provider].
+///
+/// See [EpochIteratorExt::convert_with].
+#[derive(Debug, Clone)]
+pub struct ConvertWith<'a, I, T, P> {
+    iter: I,
+    provider: &'a P,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<I, S, T, P> Iterator for ConvertWith<'_, I, T, P>
+where
+    I: Iterator<Item = Epoch<S>>,
+    Epoch<S>: ToScaleWith<T>,
+    P: Provider,
+{
+    type Item = Option<Epoch<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.iter.next()?.to_scale_with(self.provider))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator that converts [Epoch]s statelessly.
+///
+/// See [EpochIteratorExt::convert].
+#[derive(Debug, Clone)]
+pub struct Convert<I, T> {
+    iter: I,
+    _marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<I, S, T> Iterator for Convert<I, T>
+where
+    I: Iterator<Item = Epoch<S>>,
+    Epoch<S>: ToScale<T>,
+{
+    type Item = Epoch<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.iter.next()?.to_scale())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::EmptyProvider;
+    use crate::time::{TimeDelta, TAI, TT, UTC};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn convert() {
+        let epochs = [Epoch::<TAI>::from_mjd(51_544.0), Epoch::from_mjd(51_545.0)];
+        let tt: Vec<Epoch<TT>> = epochs.iter().copied().convert().collect();
+        let offset = TimeDelta::<TT>::new(32, 184_000_000).unwrap();
+        assert_eq!(tt, [epochs[0].to_scale(), epochs[1].to_scale()]);
+        assert_eq!(tt[0], epochs[0].transmute() + offset);
+    }
+
+    #[test]
+    fn convert_with() {
+        let epochs = [Epoch::<TAI>::from_mjd(51_544.0), Epoch::from_mjd(51_545.0)];
+        let mut iter = epochs.into_iter().convert_with::<UTC, _>(&EmptyProvider);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next(), Some(None));
+        assert_eq!(iter.next(), Some(None));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_with_data() {
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (51_544.0, 32, 0.0),
+            (51_545.0, 32, 0.0),
+            (51_546.0, 32, 0.0),
+        ]);
+        let utc: Vec<Epoch<UTC>> = [Epoch::<UTC>::from_mjd(51_544.5)]
+            .into_iter()
+            .convert_with::<TAI, _>(&provider)
+            .flatten()
+            .convert_with::<UTC, _>(&provider)
+            .collect::<Option<_>>()
+            .unwrap();
+        assert_eq!(utc, [Epoch::from_mjd(51_544.5)]);
+    }
+}
@@ -1,12 +1,23 @@
 //! Time [Scale]s and [Epoch]s.
 
+mod dynamic;
 pub mod epoch;
 mod epoch_type;
+mod gps;
+#[cfg(feature = "hifitime")]
+mod hifitime;
+mod iter;
 pub mod name;
+mod parse;
 mod scale;
+mod sidereal;
 mod time_delta;
 
+pub use dynamic::*;
 pub use epoch_type::*;
+pub use gps::*;
+pub use iter::*;
+pub use parse::*;
 pub use scale::*;
 pub use time_delta::*;
 
@@ -27,3 +38,9 @@ pub const SECS_PER_HOUR: u32 = 3_600;
 
 /// 1 d = 86,400 s
 pub const SECS_PER_DAY: u32 = 86_400;
+
+/// 1 w = 604,800 s
+pub const SECS_PER_WEEK: u32 = 604_800;
+
+/// 1 Julian century = 36,525 d
+pub const DAYS_PER_JULIAN_CENTURY: u32 = 36_525;
@@ -11,11 +11,23 @@ pub(super) const fn name_gregorian(
     hour: u32,
     min: u32,
     sec: u32,
+) -> NaiveDateTime {
+    name_gregorian_nanos(year, month, day, hour, min, sec, 0)
+}
+
+pub(super) const fn name_gregorian_nanos(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    min: u32,
+    sec: u32,
+    nano: u32,
 ) -> NaiveDateTime {
     match chrono::NaiveDate::from_ymd_opt(year, month, day) {
-        Some(date) => match date.and_hms_opt(hour, min, sec) {
+        Some(date) => match date.and_hms_nano_opt(hour, min, sec, nano) {
             Some(datetime) => datetime,
-            None => panic!("bad hour, minute, or second"),
+            None => panic!("bad hour, minute, second, or nanosecond"),
         },
         None => panic!("bad year, month, or day"),
     }
@@ -29,8 +41,19 @@ const fn name_julian(
     min: u32,
     sec: u32,
 ) -> NaiveDateTime {
+    match julian_to_gregorian(year, month, day) {
+        Some(date) => match date.and_hms_opt(hour, min, sec) {
+            Some(datetime) => datetime,
+            None => panic!("bad hour, minute, or second"),
+        },
+        None => panic!("bad year, month, or day"),
+    }
+}
+
+// month number to julian's month type
+const fn julian_month(month: u32) -> Option<julian::Month> {
     use julian::Month::*;
-    let month = match month {
+    Some(match month {
         1 => January,
         2 => February,
         3 => March,
@@ -43,25 +66,45 @@ const fn name_julian(
         10 => October,
         11 => November,
         12 => December,
-        _ => panic!("bad month"),
+        _ => return None,
+    })
+}
+
+// convert a proleptic Julian date to proleptic Gregorian
+pub(super) const fn julian_to_gregorian(
+    year: i32,
+    month: u32,
+    day: u32,
+) -> Option<chrono::NaiveDate> {
+    let month = match julian_month(month) {
+        Some(month) => month,
+        None => return None,
     };
 
     match julian::Calendar::JULIAN.at_ymd(year, month, day) {
         Ok(jdate) => {
             let jdate = jdate.convert_to(julian::Calendar::GREGORIAN);
-            name_gregorian(
-                jdate.year(),
-                jdate.month().number(),
-                jdate.day(),
-                hour,
-                min,
-                sec,
-            )
+            chrono::NaiveDate::from_ymd_opt(jdate.year(), jdate.month().number(), jdate.day())
         }
-        Err(_) => panic!("bad year, month, or day"),
+        Err(_) => None,
     }
 }
 
+// convert a proleptic Gregorian date to proleptic Julian year, month, day
+pub(super) fn gregorian_to_julian(date: chrono::NaiveDate) -> (i32, u32, u32) {
+    use chrono::Datelike;
+    let gdate = julian_month(date.month())
+        .and_then(|month| {
+            julian::Calendar::GREGORIAN
+                .at_ymd(date.year(), month, date.day())
+                .ok()
+        })
+        .expect("chrono date is not a valid Gregorian date");
+
+    let jdate = gdate.convert_to(julian::Calendar::JULIAN);
+    (jdate.year(), jdate.month().number(), jdate.day())
+}
+
 /// The reference date for this crate.
 ///
 /// January 1, 1900 CE at 00:00 (proleptic Gregorian).
@@ -76,3 +119,13 @@ pub const JULIAN_DAY_0: NaiveDateTime = name_julian(-4712, 1, 1, 12, 0, 0);
 ///
 /// November 17, 1858 CE at 00:00 (proleptic Gregorian).
 pub const MODIFIED_JULIAN_DAY_0: NaiveDateTime = name_gregorian(1858, 11, 17, 0, 0, 0);
+
+/// GPS week 0.
+///
+/// January 6, 1980 CE at 00:00 (proleptic Gregorian).
+pub const GPS_WEEK_0: NaiveDateTime = name_gregorian(1980, 1, 6, 0, 0, 0);
+
+/// The Unix epoch.
+///
+/// January 1, 1970 CE at 00:00 (proleptic Gregorian).
+pub const UNIX_EPOCH_0: NaiveDateTime = name_gregorian(1970, 1, 1, 0, 0, 0);
@@ -0,0 +1,432 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use super::{
+    Epoch, Scale, TimeDelta, NANOS_PER_SEC, SECS_PER_DAY, SECS_PER_HOUR, SECS_PER_MIN, UTC,
+};
+
+/// An error encountered while parsing an [Epoch] from a string.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum EpochParseError {
+    #[error("could not parse epoch {input:?}: {source}")]
+    Invalid {
+        /// The original input string.
+        input: String,
+        /// Byte offset into `input` where parsing failed, if known.
+        offset: Option<usize>,
+        /// The underlying chrono error.
+        source: chrono::ParseError,
+    },
+    #[error("epoch {input:?} has scale {found:?}, expected {expected:?}")]
+    ScaleMismatch {
+        /// The original input string.
+        input: String,
+        /// Byte offset into `input` where the scale suffix starts.
+        offset: usize,
+        /// The scale suffix found in the input.
+        found: String,
+        /// The scale suffix that was expected.
+        expected: &'static str,
+    },
+}
+
+impl EpochParseError {
+    /// The original input string that failed to parse.
+    pub fn input(&self) -> &str {
+        match self {
+            Self::Invalid { input, .. } => input,
+            Self::ScaleMismatch { input, .. } => input,
+        }
+    }
+
+    /// Byte offset into the input where parsing failed, if known.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Self::Invalid { offset, .. } => *offset,
+            Self::ScaleMismatch { offset, .. } => Some(*offset),
+        }
+    }
+}
+
+/// An error encountered while parsing a [TimeDelta] from a string.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum TimeDeltaParseError {
+    #[error("could not parse time delta {input:?}: {reason}")]
+    Invalid {
+        /// The original input string.
+        input: String,
+        /// What was wrong with the input.
+        reason: &'static str,
+    },
+    #[error("time delta {0:?} is out of range")]
+    OutOfRange(String),
+}
+
+/// Parses the format produced by [Display][core::fmt::Display], for
+/// example `2000-01-01 12:00:00 TT`.
+///
+/// The date and time may also be separated by `T`. The scale suffix
+/// is optional, and may also be written in brackets as produced by
+/// [Epoch::to_iso8601_with_scale], but if present it must match the
+/// [Scale] being parsed.
+impl<S> core::str::FromStr for Epoch<S>
+where
+    S: Scale,
+{
+    type Err = EpochParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use chrono::format::{parse_and_remainder, Parsed, StrftimeItems};
+
+        let invalid = |offset, source| EpochParseError::Invalid {
+            input: s.to_owned(),
+            offset,
+            source,
+        };
+
+        // parse in stages, so we know roughly where a failure happened
+        let mut parsed = Parsed::new();
+        let rest = parse_and_remainder(&mut parsed, s, StrftimeItems::new("%Y-%m-%d"))
+            .map_err(|e| invalid(Some(0), e))?;
+        let rest = rest.strip_prefix(['T', ' ']).unwrap_or(rest).trim_start();
+        let time_offset = s.len() - rest.len();
+        let rest = parse_and_remainder(&mut parsed, rest, StrftimeItems::new("%H:%M:%S%.f"))
+            .map_err(|e| invalid(Some(time_offset), e))?;
+        let name = parsed
+            .to_naive_datetime_with_offset(0)
+            .map_err(|e| invalid(None, e))?;
+
+        // scale suffix, either " TT" or "[TT]"
+        let trimmed = rest.trim();
+        let suffix = trimmed
+            .strip_prefix('[')
+            .and_then(|suffix| suffix.strip_suffix(']'))
+            .unwrap_or(trimmed);
+        if !suffix.is_empty() && suffix != S::NAME {
+            return Err(EpochParseError::ScaleMismatch {
+                input: s.to_owned(),
+                offset: s.len() - rest.trim_start().len(),
+                found: suffix.to_owned(),
+                expected: S::NAME,
+            });
+        }
+
+        Ok(Self::from_name(name))
+    }
+}
+
+impl Epoch<UTC> {
+    /// Parse an RFC 3339 timestamp, such as
+    /// `2023-06-01T12:34:56.789Z`.
+    ///
+    /// Offsets other than `Z` are applied. Fractional seconds are
+    /// kept to the nanosecond. A leap second such as `23:59:60.5` is
+    /// accepted, but [Epoch] cannot represent it, so it becomes
+    /// `00:00:00.5` of the following day.
+    pub fn from_rfc3339(s: &str) -> Result<Self, EpochParseError> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|datetime| Self::from_chrono(&datetime))
+            .map_err(|source| EpochParseError::Invalid {
+                input: s.to_owned(),
+                offset: None,
+                source,
+            })
+    }
+
+    /// Parse an ISO 8601 timestamp in extended format, such as
+    /// `2023-06-01T12:34:56.789+05:30`.
+    ///
+    /// This is more lenient than [from_rfc3339][Self::from_rfc3339]:
+    /// offsets may omit minutes, and timestamps with no offset are
+    /// taken to be in UTC. Leap seconds are handled the same way.
+    pub fn from_iso8601(s: &str) -> Result<Self, EpochParseError> {
+        match chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%#z") {
+            Ok(datetime) => Ok(Self::from_chrono(&datetime)),
+            Err(_) => s.parse(),
+        }
+    }
+}
+
+// split off an optional leading sign, returning -1 or 1
+fn split_sign(s: &str) -> (i128, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (-1, rest)
+    } else {
+        (1, s.strip_prefix('+').unwrap_or(s))
+    }
+}
+
+// whole seconds in nanoseconds
+fn secs_to_nanos(secs: u32) -> i128 {
+    secs as i128 * NANOS_PER_SEC as i128
+}
+
+// parse a non-empty run of ascii digits
+fn parse_digits(s: &str) -> Option<i128> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+// parse seconds with an optional fraction of up to nine digits, into
+// nanoseconds
+fn parse_seconds(s: &str) -> Option<i128> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let mut nanos = parse_digits(whole)?.checked_mul(secs_to_nanos(1))?;
+    if s.contains('.') {
+        if fraction.len() > 9 {
+            return None;
+        }
+        let scale = 10i128.pow(9 - fraction.len() as u32);
+        nanos += parse_digits(fraction)? * scale;
+    }
+    Some(nanos)
+}
+
+impl<S> TimeDelta<S> {
+    /// Parse signed hours, minutes, and seconds, such as
+    /// `01:23:45.5` or `-49:00:00`.
+    ///
+    /// This reads the format produced by
+    /// [to_hms_string][Self::to_hms_string]. Hours may be any
+    /// length, minutes and seconds must be less than 60, and
+    /// fractional seconds may have up to nine digits.
+    pub fn from_hms_str(s: &str) -> Result<Self, TimeDeltaParseError> {
+        let invalid = |reason| TimeDeltaParseError::Invalid {
+            input: s.to_owned(),
+            reason,
+        };
+
+        let (sign, rest) = split_sign(s.trim());
+        let mut parts = rest.split(':');
+        let (Some(hours), Some(minutes), Some(seconds), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("expected hours, minutes, and seconds"));
+        };
+
+        let hours = parse_digits(hours).ok_or_else(|| invalid("bad hours"))?;
+        let minutes = parse_digits(minutes)
+            .filter(|m| *m < SECS_PER_MIN as i128)
+            .ok_or_else(|| invalid("bad minutes"))?;
+        let seconds = parse_seconds(seconds)
+            .filter(|s| *s < secs_to_nanos(SECS_PER_MIN))
+            .ok_or_else(|| invalid("bad seconds"))?;
+
+        let nanos = hours
+            .checked_mul(secs_to_nanos(SECS_PER_HOUR))
+            .and_then(|n| n.checked_add(minutes * secs_to_nanos(SECS_PER_MIN)))
+            .and_then(|n| n.checked_add(seconds));
+        nanos
+            .and_then(|n| Self::from_total_nanos(sign * n))
+            .ok_or_else(|| TimeDeltaParseError::OutOfRange(s.to_owned()))
+    }
+
+    /// Parse an ISO 8601 duration, such as `PT1H23M45.5S` or
+    /// `-P2DT1H`.
+    ///
+    /// This reads the format produced by
+    /// [to_iso8601_duration][Self::to_iso8601_duration]. Days are
+    /// taken to be exactly 86,400 s. Years and months have no fixed
+    /// length, so they are rejected. Only seconds may have a
+    /// fraction.
+    pub fn from_iso8601_duration(s: &str) -> Result<Self, TimeDeltaParseError> {
+        let invalid = |reason| TimeDeltaParseError::Invalid {
+            input: s.to_owned(),
+            reason,
+        };
+
+        let (sign, rest) = split_sign(s.trim());
+        let rest = rest
+            .strip_prefix('P')
+            .ok_or_else(|| invalid("expected a leading 'P'"))?;
+        let (date, time) = match rest.split_once('T') {
+            Some((_, "")) => return Err(invalid("expected a time after 'T'")),
+            Some((date, time)) => (date, time),
+            None => (rest, ""),
+        };
+        if date.is_empty() && time.is_empty() {
+            return Err(invalid("expected at least one component"));
+        }
+
+        // each component is a number followed by its designator, in
+        // this order, each at most once
+        let mut nanos: Option<i128> = Some(0);
+        for (part, designators) in [(date, "D"), (time, "HMS")] {
+            let mut part = part;
+            let mut allowed = designators;
+            while !part.is_empty() {
+                let end = part
+                    .find(|c: char| !c.is_ascii_digit() && c != '.')
+                    .ok_or_else(|| invalid("missing designator"))?;
+                let number = &part[..end];
+                let designator = part[end..].chars().next().unwrap_or_default();
+                if matches!(designator, 'Y' | 'M' | 'W') && designators == "D" {
+                    return Err(invalid("years, months, and weeks are not supported"));
+                }
+
+                let index = allowed
+                    .find(designator)
+                    .ok_or_else(|| invalid("unexpected or repeated designator"))?;
+                allowed = &allowed[index + 1..];
+
+                let component = if designator == 'S' {
+                    parse_seconds(number).ok_or_else(|| invalid("bad seconds"))?
+                } else {
+                    let unit = match designator {
+                        'D' => SECS_PER_DAY,
+                        'H' => SECS_PER_HOUR,
+                        _ => SECS_PER_MIN,
+                    };
+                    parse_digits(number)
+                        .ok_or_else(|| invalid("bad number"))?
+                        .checked_mul(secs_to_nanos(unit))
+                        .ok_or_else(|| TimeDeltaParseError::OutOfRange(s.to_owned()))?
+                };
+                nanos = nanos.and_then(|n| n.checked_add(component));
+                part = &part[end + designator.len_utf8()..];
+            }
+        }
+
+        nanos
+            .and_then(|n| Self::from_total_nanos(sign * n))
+            .ok_or_else(|| TimeDeltaParseError::OutOfRange(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::TT;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_epoch() {
+        let expected = Epoch::<TT>::from_name(
+            chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+                .unwrap()
+                .and_hms_milli_opt(12, 0, 0, 250)
+                .unwrap(),
+        );
+
+        for s in [
+            "2000-01-01 12:00:00.25 TT",
+            "2000-01-01T12:00:00.25",
+            "2000-01-01T12:00:00.250000000[TT]",
+        ] {
+            assert_eq!(s.parse::<Epoch<TT>>(), Ok(expected), "{s}");
+        }
+        assert_eq!(expected.to_string().parse(), Ok(expected));
+    }
+
+    #[test]
+    fn parse_epoch_errors() {
+        let err = "2000-01-01 12:00:00 TAI".parse::<Epoch<TT>>().unwrap_err();
+        assert_eq!(err.input(), "2000-01-01 12:00:00 TAI");
+        assert_eq!(err.offset(), Some(20));
+        assert!(matches!(
+            err,
+            EpochParseError::ScaleMismatch { ref found, expected: "TT", .. } if found == "TAI"
+        ));
+
+        let err = "2000-13-01 12:00:00".parse::<Epoch<TT>>().unwrap_err();
+        assert_eq!(err.offset(), Some(0));
+
+        let err = "2000-01-01 12:xx:00".parse::<Epoch<TT>>().unwrap_err();
+        assert_eq!(err.offset(), Some(11));
+    }
+
+    #[test]
+    fn rfc3339() {
+        use crate::time::UTC;
+
+        let expected =
+            Epoch::<UTC>::from_gregorian_nanos(2023, 6, 1, 12, 34, 56, 789_000_000).unwrap();
+        assert_eq!(
+            Epoch::from_rfc3339("2023-06-01T12:34:56.789Z"),
+            Ok(expected)
+        );
+        assert_eq!(
+            Epoch::from_rfc3339("2023-06-01T18:04:56.789+05:30"),
+            Ok(expected)
+        );
+
+        // leap seconds become the start of the next day
+        let leap = Epoch::from_rfc3339("2016-12-31T23:59:60.5Z").unwrap();
+        assert_eq!(
+            leap,
+            Epoch::from_gregorian_nanos(2017, 1, 1, 0, 0, 0, 500_000_000).unwrap()
+        );
+
+        let err = Epoch::<UTC>::from_rfc3339("2023-06-01T12:34:56").unwrap_err();
+        assert_eq!(err.input(), "2023-06-01T12:34:56");
+    }
+
+    #[test]
+    fn iso8601() {
+        use crate::time::UTC;
+
+        let expected = Epoch::<UTC>::from_gregorian(2023, 6, 1, 12, 34, 56).unwrap();
+        for s in [
+            "2023-06-01T12:34:56Z",
+            "2023-06-01T17:34:56+05",
+            "2023-06-01T17:34:56+05:00",
+            "2023-06-01T12:34:56",
+        ] {
+            assert_eq!(Epoch::from_iso8601(s), Ok(expected), "{s}");
+        }
+        assert!(Epoch::<UTC>::from_iso8601("2023-06-01").is_err());
+    }
+
+    #[test]
+    fn hms_str() {
+        let parse = TimeDelta::<TT>::from_hms_str;
+        let delta = TimeDelta::new(3_600 + 23 * 60 + 45, 500_000_000).unwrap();
+        assert_eq!(parse("01:23:45.5"), Ok(delta));
+        assert_eq!(parse("+01:23:45.5"), Ok(delta));
+        assert_eq!(
+            parse("-49:00:00"),
+            Ok(TimeDelta::new(-49 * 3_600, 0).unwrap())
+        );
+        assert_eq!(parse(&delta.to_hms_string()), Ok(delta));
+
+        for bad in [
+            "01:60:00",
+            "01:00:60",
+            "01:00",
+            "1:2:3:4",
+            "01:00:00.1234567890",
+            "aa:00:00",
+        ] {
+            assert!(
+                matches!(parse(bad), Err(TimeDeltaParseError::Invalid { .. })),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn iso8601_duration() {
+        let parse = TimeDelta::<TT>::from_iso8601_duration;
+        let delta = TimeDelta::new(3_600 + 23 * 60 + 45, 678_000_000).unwrap();
+        assert_eq!(parse("PT1H23M45.678S"), Ok(delta));
+        assert_eq!(parse(&delta.to_iso8601_duration()), Ok(delta));
+        assert_eq!(
+            parse("-P2DT1H"),
+            Ok(TimeDelta::new(-(2 * 86_400 + 3_600), 0).unwrap())
+        );
+        assert_eq!(parse("P1D"), Ok(TimeDelta::new(86_400, 0).unwrap()));
+
+        for bad in ["PT", "P", "1H", "PT1M1H", "PT1H1H", "P1Y", "P1W", "PT1"] {
+            assert!(
+                matches!(parse(bad), Err(TimeDeltaParseError::Invalid { .. })),
+                "{bad}"
+            );
+        }
+        assert!(matches!(
+            parse("P99999999999999999D"),
+            Err(TimeDeltaParseError::OutOfRange(_))
+        ));
+    }
+}
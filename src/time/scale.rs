@@ -1,5 +1,7 @@
+use super::epoch::origins::TAI_1977;
+use super::epoch::J2000;
 use super::{Epoch, TimeDelta, NANOS_PER_MILLI};
-use crate::provider::{EmptyProvider, Provider};
+use crate::provider::{EmptyProvider, Provider, TracedProvider};
 
 // augh const fn
 const fn time_delta<S>(secs: i64, nanos: u32) -> TimeDelta<S> {
@@ -10,6 +12,8 @@ const fn time_delta<S>(secs: i64, nanos: u32) -> TimeDelta<S> {
 }
 
 /// A timescale in which to measure [Epoch][super::Epoch]s and [TimeDelta]s.
+///
+/// To pick a scale by name at runtime, see [ScaleKind::from_name].
 pub trait Scale {
     /// The name of this timescale, used in [core::fmt::Display].
     const NAME: &'static str;
@@ -25,6 +29,20 @@ pub trait ToScaleWith<Other> {
     fn to_scale_with<P>(&self, provider: &P) -> Option<Epoch<Other>>
     where
         P: Provider;
+
+    /// Convert the given [Epoch] into a new [Scale], and also return
+    /// whether the [Provider] was consulted.
+    ///
+    /// Results that did not consult the provider do not depend on
+    /// it, and can be cached even if the provider's data changes.
+    fn to_scale_traced_with<P>(&self, provider: &P) -> Option<(Epoch<Other>, bool)>
+    where
+        P: Provider,
+    {
+        let traced = TracedProvider::new(provider);
+        let epoch = self.to_scale_with(&traced)?;
+        Some((epoch, traced.consulted()))
+    }
 }
 
 /// Convert an [Epoch] from one [Scale] to another, using an Earth
@@ -93,6 +111,15 @@ macro_rules! impl_to_tai_family {
     };
 }
 
+// given ToScaleWith<TT>, implement ToScaleWith<TtLike>
+macro_rules! impl_to_tt_family {
+    ($Trait:tt, $Scale:tt) => {
+        impl_to_via!($Trait, $Scale, TT, TDB);
+        impl_to_via!($Trait, $Scale, TT, TCG);
+        impl_to_via!($Trait, $Scale, TT, TCB);
+    };
+}
+
 /// International Atomic Time (*temps atomique international*).
 pub struct TAI;
 
@@ -200,6 +227,10 @@ impl ToScaleWith<TAI> for Epoch<UTC> {
 impl_to_tai_family!(ToScaleWith, UTC);
 
 /// Universal Time.
+///
+/// UT1 follows the rotation of the Earth, so conversions to and from
+/// UT1 always need Earth orientation data from a [Provider]. There is
+/// no stateless [ToScale] for this scale.
 pub struct UT1;
 
 impl Scale for UT1 {
@@ -229,3 +260,409 @@ impl ToScaleWith<UT1> for Epoch<UTC> {
 impl_to_via!(ToScaleWith, TAI, UTC, UT1);
 impl_to_via!(ToScaleWith, UT1, UTC, TAI);
 impl_to_tai_family!(ToScaleWith, UT1);
+
+/// Barycentric Dynamical Time (*temps dynamique barycentrique*).
+pub struct TDB;
+
+impl Scale for TDB {
+    const NAME: &'static str = "TDB";
+}
+
+// TDB - TT in seconds, given days since J2000 in either scale. This
+// uses only the two largest periodic terms, and is accurate to about
+// 30 us.
+fn tdb_tt_seconds(days: f64) -> f64 {
+    // Earth's mean anomaly
+    let g = (357.53 + 0.98560028 * days).to_radians();
+    0.001657 * libm::sin(g) + 0.000014 * libm::sin(2.0 * g)
+}
+
+impl ToScaleWith<TDB> for Epoch<TT> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TDB>>
+    where
+        P: Provider,
+    {
+        let days = (*self - J2000).to_days();
+        Some(self.transmute() + TimeDelta::from_seconds(tdb_tt_seconds(days)))
+    }
+}
+
+impl ToScale<TDB> for Epoch<TT> {}
+
+impl ToScaleWith<TT> for Epoch<TDB> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TT>>
+    where
+        P: Provider,
+    {
+        // TDB - TT changes slowly enough that evaluating it at TDB is
+        // well within its own accuracy
+        let days = (self.transmute() - J2000).to_days();
+        Some((*self - TimeDelta::from_seconds(tdb_tt_seconds(days))).transmute())
+    }
+}
+
+impl ToScale<TT> for Epoch<TDB> {}
+
+impl_to_via!(ToScale, TDB, TT, TAI);
+impl_to_via!(ToScale, TDB, TT, GPS);
+impl_to_via!(ToScaleWith, TDB, TT, UTC);
+impl_to_via!(ToScaleWith, TDB, TT, UT1);
+
+/// Geocentric Coordinate Time (*temps-coordonnée géocentrique*).
+pub struct TCG;
+
+impl Scale for TCG {
+    const NAME: &'static str = "TCG";
+}
+
+// rate of TCG relative to TT, IAU 2000 resolution B1.9
+const TCG_TT_RATE: f64 = 6.969290134e-10;
+
+// 1977-01-01 00:00:00 TAI, expressed in TT, TCG, TDB, or TCB
+fn coordinate_time_epoch<S>() -> Epoch<S> {
+    (TAI_1977.transmute::<TT>() + TT_TAI_OFFSET).transmute()
+}
+
+impl ToScaleWith<TCG> for Epoch<TT> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TCG>>
+    where
+        P: Provider,
+    {
+        let elapsed = (*self - coordinate_time_epoch()).to_seconds();
+        let offset = TCG_TT_RATE / (1.0 - TCG_TT_RATE) * elapsed;
+        Some(self.transmute() + TimeDelta::from_seconds(offset))
+    }
+}
+
+impl ToScale<TCG> for Epoch<TT> {}
+
+impl ToScaleWith<TT> for Epoch<TCG> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TT>>
+    where
+        P: Provider,
+    {
+        let elapsed = (*self - coordinate_time_epoch()).to_seconds();
+        let offset = TCG_TT_RATE * elapsed;
+        Some((*self - TimeDelta::from_seconds(offset)).transmute())
+    }
+}
+
+impl ToScale<TT> for Epoch<TCG> {}
+
+impl_to_via!(ToScale, TCG, TT, TAI);
+impl_to_via!(ToScale, TCG, TT, GPS);
+impl_to_via!(ToScaleWith, TCG, TT, UTC);
+impl_to_via!(ToScaleWith, TCG, TT, UT1);
+
+/// Barycentric Coordinate Time (*temps-coordonnée barycentrique*).
+///
+/// This is defined exactly in terms of [TDB], but the conversion from
+/// [TDB] to [TT] in this crate is only accurate to about 30 us, and
+/// so conversions to and from TCB are too.
+pub struct TCB;
+
+impl Scale for TCB {
+    const NAME: &'static str = "TCB";
+}
+
+// rate of TCB relative to TDB, IAU 2006 resolution B3
+const TCB_TDB_RATE: f64 = 1.550519768e-8;
+
+// TDB - TCB at the coordinate time epoch, IAU 2006 resolution B3
+const TDB_0: f64 = -6.55e-5;
+
+impl ToScaleWith<TCB> for Epoch<TDB> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TCB>>
+    where
+        P: Provider,
+    {
+        let elapsed = (*self - coordinate_time_epoch()).to_seconds();
+        let offset = (TCB_TDB_RATE * elapsed - TDB_0) / (1.0 - TCB_TDB_RATE);
+        Some(self.transmute() + TimeDelta::from_seconds(offset))
+    }
+}
+
+impl ToScale<TCB> for Epoch<TDB> {}
+
+impl ToScaleWith<TDB> for Epoch<TCB> {
+    fn to_scale_with<P>(&self, _provider: &P) -> Option<Epoch<TDB>>
+    where
+        P: Provider,
+    {
+        let elapsed = (*self - coordinate_time_epoch()).to_seconds();
+        let offset = TCB_TDB_RATE * elapsed - TDB_0;
+        Some((*self - TimeDelta::from_seconds(offset)).transmute())
+    }
+}
+
+impl ToScale<TDB> for Epoch<TCB> {}
+
+impl_to_via!(ToScale, TT, TDB, TCB);
+impl_to_via!(ToScale, TCB, TDB, TT);
+impl_to_via!(ToScale, TCB, TT, TAI);
+impl_to_via!(ToScale, TCB, TT, GPS);
+impl_to_via!(ToScaleWith, TCB, TT, UTC);
+impl_to_via!(ToScaleWith, TCB, TT, UT1);
+impl_to_via!(ToScale, TCB, TT, TCG);
+impl_to_via!(ToScale, TDB, TT, TCG);
+
+impl_to_tt_family!(ToScale, TAI);
+impl_to_tt_family!(ToScale, GPS);
+impl_to_tt_family!(ToScaleWith, UTC);
+impl_to_tt_family!(ToScaleWith, UT1);
+impl_to_tt_family!(ToScale, TCG);
+
+/// A user-defined [Scale] that converts to and from [TAI].
+///
+/// Implement this for your own scale, such as a spacecraft clock,
+/// and [ToScaleWith] is implemented between it and every built-in
+/// scale by going through [TAI]. Implementing [ToScaleWith] directly
+/// for your own scale is not possible outside this crate, because
+/// [Epoch] is not local to your crate.
+///
+/// Conversions between two custom scales are not provided.
+pub trait CustomScale: Scale + Sized {
+    /// Convert an [Epoch] in this scale to [TAI], using an Earth
+    /// orientation [Provider].
+    fn to_tai_with<P>(epoch: &Epoch<Self>, provider: &P) -> Option<Epoch<TAI>>
+    where
+        P: Provider;
+
+    /// Convert an [Epoch] in [TAI] to this scale, using an Earth
+    /// orientation [Provider].
+    fn from_tai_with<P>(epoch: &Epoch<TAI>, provider: &P) -> Option<Epoch<Self>>
+    where
+        P: Provider;
+}
+
+impl<S> ToScaleWith<TAI> for Epoch<S>
+where
+    S: CustomScale,
+{
+    fn to_scale_with<P>(&self, provider: &P) -> Option<Epoch<TAI>>
+    where
+        P: Provider,
+    {
+        S::to_tai_with(self, provider)
+    }
+}
+
+impl<S> ToScaleWith<S> for Epoch<TAI>
+where
+    S: CustomScale,
+{
+    fn to_scale_with<P>(&self, provider: &P) -> Option<Epoch<S>>
+    where
+        P: Provider,
+    {
+        S::from_tai_with(self, provider)
+    }
+}
+
+// given CustomScale, implement ToScaleWith in both directions via TAI
+macro_rules! impl_custom_via_tai {
+    ($($End:tt),*) => {
+        $(
+            impl<S> ToScaleWith<$End> for Epoch<S>
+            where
+                S: CustomScale,
+            {
+                fn to_scale_with<P>(&self, provider: &P) -> Option<Epoch<$End>>
+                where
+                    P: Provider,
+                {
+                    let middle: Epoch<TAI> = self.to_scale_with(provider)?;
+                    middle.to_scale_with(provider)
+                }
+            }
+
+            impl<S> ToScaleWith<S> for Epoch<$End>
+            where
+                S: CustomScale,
+            {
+                fn to_scale_with<P>(&self, provider: &P) -> Option<Epoch<S>>
+                where
+                    P: Provider,
+                {
+                    let middle: Epoch<TAI> = self.to_scale_with(provider)?;
+                    middle.to_scale_with(provider)
+                }
+            }
+        )*
+    };
+}
+
+impl_custom_via_tai!(TT, GPS, UTC, UT1, TDB, TCG, TCB);
+
+/// A [Scale], chosen at runtime.
+///
+/// Each variant corresponds to the [Scale] type of the same name.
+/// This is useful when the scale comes from user input, such as a
+/// command line flag. See [DynEpoch][super::DynEpoch] and
+/// [convert_dynamic][super::convert_dynamic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScaleKind {
+    TAI,
+    TT,
+    GPS,
+    UTC,
+    UT1,
+    TDB,
+    TCG,
+    TCB,
+}
+
+impl ScaleKind {
+    /// Every known [ScaleKind].
+    pub const ALL: [Self; 8] = [
+        Self::TAI,
+        Self::TT,
+        Self::GPS,
+        Self::UTC,
+        Self::UT1,
+        Self::TDB,
+        Self::TCG,
+        Self::TCB,
+    ];
+
+    /// Look up a [ScaleKind] by its [Scale::NAME], ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The [Scale::NAME] of this scale.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::TAI => TAI::NAME,
+            Self::TT => TT::NAME,
+            Self::GPS => GPS::NAME,
+            Self::UTC => UTC::NAME,
+            Self::UT1 => UT1::NAME,
+            Self::TDB => TDB::NAME,
+            Self::TCG => TCG::NAME,
+            Self::TCB => TCB::NAME,
+        }
+    }
+}
+
+impl core::fmt::Display for ScaleKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tdb_round_trip() {
+        for day in (0..366).step_by(5) {
+            let tt = J2000 + TimeDelta::from_days(day as f64 + 0.25);
+            let tdb = tt.convert_to::<TDB>();
+            let back = tdb.convert_to::<TT>();
+            assert!((back - tt).to_seconds().abs() < 1e-6);
+
+            // TDB - TT never exceeds about 1.7 ms
+            let offset = (tdb.transmute::<TT>() - tt).to_seconds();
+            assert!(offset.abs() < 1.7e-3);
+        }
+
+        // about -73 us at J2000
+        let offset = (J2000.convert_to::<TDB>().transmute::<TT>() - J2000).to_seconds();
+        assert!((offset + 73e-6).abs() < 30e-6);
+    }
+
+    #[test]
+    fn tcg_at_j2000() {
+        // TCG - TT is about 0.5058 s at J2000
+        let tcg = J2000.convert_to::<TCG>();
+        let offset = (tcg.transmute::<TT>() - J2000).to_seconds();
+        assert!((offset - 0.5058).abs() < 1e-4);
+        assert_eq!(tcg.convert_to::<TT>(), J2000);
+
+        // and zero at 1977-01-01 00:00:00 TAI
+        let tt_1977 = TAI_1977.convert_to::<TT>();
+        assert_eq!(tt_1977.convert_to::<TCG>().transmute(), tt_1977);
+    }
+
+    #[test]
+    fn tcb_at_j2000() {
+        // TCB - TDB is about 11.2538 s at J2000
+        let tdb = J2000.convert_to::<TDB>();
+        let tcb = tdb.convert_to::<TCB>();
+        let offset = (tcb.transmute::<TDB>() - tdb).to_seconds();
+        assert!((offset - 11.2538).abs() < 1e-4);
+
+        let back = tcb.convert_to::<TDB>();
+        assert!((back - tdb).to_seconds().abs() < 1e-6);
+        let back = tcb.convert_to::<TT>();
+        assert!((back - J2000).to_seconds().abs() < 1e-6);
+    }
+
+    #[test]
+    fn ut1_needs_provider() {
+        let utc = Epoch::<UTC>::from_mjd(51_544.5);
+        assert_eq!(utc.to_ut1_with(&EmptyProvider), None);
+        assert_eq!(J2000.to_ut1_with(&EmptyProvider), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn ut1_with_provider() {
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (51_544.0, 32, 0.355),
+            (51_545.0, 32, 0.355),
+        ]);
+        let utc = Epoch::<UTC>::from_mjd(51_544.5);
+        let ut1 = utc.to_ut1_with(&provider).unwrap();
+        assert_eq!(
+            ut1,
+            utc.transmute() + TimeDelta::new(0, 355_000_000).unwrap()
+        );
+        assert_eq!(ut1.to_utc_with(&provider), Some(utc));
+    }
+
+    // a clock running exactly 100 s ahead of TAI
+    struct Ahead;
+
+    impl Scale for Ahead {
+        const NAME: &'static str = "AHEAD";
+    }
+
+    impl CustomScale for Ahead {
+        fn to_tai_with<P>(epoch: &Epoch<Self>, _provider: &P) -> Option<Epoch<TAI>>
+        where
+            P: Provider,
+        {
+            Some(epoch.transmute() - TimeDelta::new(100, 0)?)
+        }
+
+        fn from_tai_with<P>(epoch: &Epoch<TAI>, _provider: &P) -> Option<Epoch<Self>>
+        where
+            P: Provider,
+        {
+            Some((*epoch + TimeDelta::new(100, 0)?).transmute())
+        }
+    }
+
+    #[test]
+    fn custom_scale_via_tai() {
+        let ahead: Epoch<Ahead> = J2000.convert(&EmptyProvider).unwrap();
+        assert_eq!(
+            ahead.transmute::<TAI>() - J2000.to_tai(),
+            TimeDelta::new(100, 0).unwrap()
+        );
+        assert_eq!(ahead.convert::<TT, _>(&EmptyProvider), Some(J2000));
+
+        // every built-in scale goes through TAI, including GPS
+        let gps: Epoch<GPS> = ahead.convert(&EmptyProvider).unwrap();
+        assert_eq!(gps, J2000.to_gps());
+        assert_eq!(gps.convert::<Ahead, _>(&EmptyProvider), Some(ahead));
+
+        // and fails where TAI does
+        assert_eq!(ahead.convert::<UTC, _>(&EmptyProvider), None);
+    }
+}
@@ -0,0 +1,294 @@
+use core::f64::consts::TAU;
+
+use super::epoch::J2000;
+use super::{Epoch, TimeDelta, NANOS_PER_SEC, SECS_PER_DAY, TT, UT1};
+use crate::provider::Provider;
+use crate::units::Arcsec;
+
+// radians per second of time, where 86,400 s is a full turn
+const RAD_PER_SEC: f64 = TAU / 86_400.0;
+
+// wrap an angle into [0, 2pi)
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = libm::fmod(angle, TAU);
+    if wrapped < 0.0 {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
+
+// IAU 1980 nutation in longitude, truncated to the terms of at least
+// 0.5 mas. each row is the multiples of the fundamental arguments D,
+// M, M', F, and Omega, and the sine coefficient and its rate, in units
+// of 0.1 mas and 0.1 mas per century.
+#[rustfmt::skip]
+const NUTATION_1980: [([f64; 5], f64, f64); 49] = [
+    ([ 0.0,  0.0,  0.0,  0.0,  1.0], -171_996.0, -174.2),
+    ([-2.0,  0.0,  0.0,  2.0,  2.0],  -13_187.0,   -1.6),
+    ([ 0.0,  0.0,  0.0,  2.0,  2.0],   -2_274.0,   -0.2),
+    ([ 0.0,  0.0,  0.0,  0.0,  2.0],    2_062.0,    0.2),
+    ([ 0.0,  1.0,  0.0,  0.0,  0.0],    1_426.0,   -3.4),
+    ([ 0.0,  0.0,  1.0,  0.0,  0.0],      712.0,    0.1),
+    ([-2.0,  1.0,  0.0,  2.0,  2.0],     -517.0,    1.2),
+    ([ 0.0,  0.0,  0.0,  2.0,  1.0],     -386.0,   -0.4),
+    ([ 0.0,  0.0,  1.0,  2.0,  2.0],     -301.0,    0.0),
+    ([-2.0, -1.0,  0.0,  2.0,  2.0],      217.0,   -0.5),
+    ([-2.0,  0.0,  1.0,  0.0,  0.0],     -158.0,    0.0),
+    ([-2.0,  0.0,  0.0,  2.0,  1.0],      129.0,    0.1),
+    ([ 0.0,  0.0, -1.0,  2.0,  2.0],      123.0,    0.0),
+    ([ 2.0,  0.0,  0.0,  0.0,  0.0],       63.0,    0.0),
+    ([ 0.0,  0.0,  1.0,  0.0,  1.0],       63.0,    0.1),
+    ([ 2.0,  0.0, -1.0,  2.0,  2.0],      -59.0,    0.0),
+    ([ 0.0,  0.0, -1.0,  0.0,  1.0],      -58.0,   -0.1),
+    ([ 0.0,  0.0,  1.0,  2.0,  1.0],      -51.0,    0.0),
+    ([-2.0,  0.0,  2.0,  0.0,  0.0],       48.0,    0.0),
+    ([ 0.0,  0.0, -2.0,  2.0,  1.0],       46.0,    0.0),
+    ([ 2.0,  0.0,  0.0,  2.0,  2.0],      -38.0,    0.0),
+    ([ 0.0,  0.0,  2.0,  2.0,  2.0],      -31.0,    0.0),
+    ([ 0.0,  0.0,  2.0,  0.0,  0.0],       29.0,    0.0),
+    ([-2.0,  0.0,  1.0,  2.0,  2.0],       29.0,    0.0),
+    ([ 0.0,  0.0,  0.0,  2.0,  0.0],       26.0,    0.0),
+    ([-2.0,  0.0,  0.0,  2.0,  0.0],      -22.0,    0.0),
+    ([ 0.0,  0.0, -1.0,  2.0,  1.0],       21.0,    0.0),
+    ([ 0.0,  2.0,  0.0,  0.0,  0.0],       17.0,   -0.1),
+    ([ 2.0,  0.0, -1.0,  0.0,  1.0],       16.0,    0.0),
+    ([-2.0,  2.0,  0.0,  2.0,  2.0],      -16.0,    0.1),
+    ([ 0.0,  1.0,  0.0,  0.0,  1.0],      -15.0,    0.0),
+    ([-2.0,  0.0,  1.0,  0.0,  1.0],      -13.0,    0.0),
+    ([ 0.0, -1.0,  0.0,  0.0,  1.0],      -12.0,    0.0),
+    ([ 0.0,  0.0,  2.0, -2.0,  0.0],       11.0,    0.0),
+    ([ 2.0,  0.0, -1.0,  2.0,  1.0],      -10.0,    0.0),
+    ([ 2.0,  0.0,  1.0,  2.0,  2.0],       -8.0,    0.0),
+    ([ 0.0,  1.0,  0.0,  2.0,  2.0],        7.0,    0.0),
+    ([-2.0,  1.0,  1.0,  0.0,  0.0],       -7.0,    0.0),
+    ([ 0.0, -1.0,  0.0,  2.0,  2.0],       -7.0,    0.0),
+    ([ 2.0,  0.0,  0.0,  2.0,  1.0],       -7.0,    0.0),
+    ([ 2.0,  0.0,  1.0,  0.0,  0.0],        6.0,    0.0),
+    ([-2.0,  0.0,  2.0,  2.0,  2.0],        6.0,    0.0),
+    ([-2.0,  0.0,  1.0,  2.0,  1.0],        6.0,    0.0),
+    ([ 2.0,  0.0, -2.0,  0.0,  1.0],       -6.0,    0.0),
+    ([ 2.0,  0.0,  0.0,  0.0,  1.0],       -6.0,    0.0),
+    ([ 0.0, -1.0,  1.0,  0.0,  0.0],        5.0,    0.0),
+    ([-2.0, -1.0,  0.0,  2.0,  1.0],       -5.0,    0.0),
+    ([-2.0,  0.0,  0.0,  0.0,  1.0],       -5.0,    0.0),
+    ([ 0.0,  0.0,  2.0,  2.0,  1.0],       -5.0,    0.0),
+];
+
+// IAU 1980 nutation in longitude, t in julian centuries of TT since
+// J2000
+fn nutation_in_longitude(t: f64) -> Arcsec {
+    // fundamental arguments, in degrees
+    let arguments = [
+        // mean elongation of the moon from the sun
+        297.850_36 + 445_267.111_480 * t - 0.001_914_2 * t * t + t * t * t / 189_474.0,
+        // mean anomaly of the sun
+        357.527_72 + 35_999.050_340 * t - 0.000_160_3 * t * t - t * t * t / 300_000.0,
+        // mean anomaly of the moon
+        134.962_98 + 477_198.867_398 * t + 0.008_697_2 * t * t + t * t * t / 56_250.0,
+        // moon's argument of latitude
+        93.271_91 + 483_202.017_538 * t - 0.003_682_5 * t * t + t * t * t / 327_270.0,
+        // longitude of the moon's ascending node
+        125.044_52 - 1_934.136_261 * t + 0.002_070_8 * t * t + t * t * t / 450_000.0,
+    ];
+
+    let sum: f64 = NUTATION_1980
+        .iter()
+        .map(|(multiples, coefficient, rate)| {
+            let argument: f64 = multiples.iter().zip(arguments).map(|(m, a)| m * a).sum();
+            (coefficient + rate * t) * libm::sin(argument.to_radians())
+        })
+        .sum();
+
+    Arcsec(sum / 10_000.0)
+}
+
+impl Epoch<UT1> {
+    /// Earth Rotation Angle, in radians within `[0, 2π)`.
+    ///
+    /// This is the IAU 2000 expression, a linear function of UT1
+    /// used by the CIO-based transformations in place of
+    /// [gmst][Self::gmst].
+    pub fn earth_rotation_angle(&self) -> f64 {
+        // split UT1 days since J2000 into whole days and a fraction,
+        // to keep precision in the fast-moving term
+        let (days, nanos) = (*self - J2000.transmute()).to_day_nanos();
+        let fraction = nanos as f64 / (SECS_PER_DAY as f64 * NANOS_PER_SEC as f64);
+        let t = days as f64 + fraction;
+
+        wrap_angle(TAU * (fraction + 0.779_057_273_264_0 + 0.002_737_811_911_354_48 * t))
+    }
+
+    /// Greenwich Mean Sidereal Time, in radians within `[0, 2π)`.
+    ///
+    /// This uses the IAU 1982 expression in terms of UT1.
+    pub fn gmst(&self) -> f64 {
+        // julian centuries of UT1 since J2000, counted in UT1
+        let t = self.transmute::<TT>().julian_centuries_since_j2000();
+
+        // seconds of time, including the 876,600 h of a whole century
+        let seconds =
+            67_310.548_41 + (876_600.0 * 3_600.0 + 8_640_184.812_866) * t + 0.093_104 * t * t
+                - 6.2e-6 * t * t * t;
+
+        wrap_angle(seconds * RAD_PER_SEC)
+    }
+
+    /// Local Mean Sidereal Time for an observer at the given east
+    /// longitude, in radians within `[0, 2π)`.
+    ///
+    /// West longitudes are negative.
+    pub fn lmst(&self, east_longitude_rad: f64) -> f64 {
+        wrap_angle(self.gmst() + east_longitude_rad)
+    }
+
+    /// Greenwich Apparent Sidereal Time, in radians within `[0, 2π)`.
+    ///
+    /// This is [gmst][Self::gmst] plus the equation of the equinoxes.
+    /// The nutation in longitude is the IAU 1980 series, plus the
+    /// correction from [Provider::nutation_corrections_for_utc], and
+    /// the obliquity is the IAU 1980 mean obliquity. Returns [None] if
+    /// the provider has no nutation data for this epoch.
+    pub fn gast<P>(&self, provider: &P) -> Option<f64>
+    where
+        P: Provider,
+    {
+        let utc = self.to_utc_with(provider)?;
+        let (dpsi, _) = provider.nutation_corrections_for_utc(&utc)?;
+        let t = self.to_tt_with(provider)?.julian_centuries_since_j2000();
+
+        // mean obliquity of the ecliptic
+        let obliquity =
+            Arcsec(84_381.448 - 46.815_0 * t - 0.000_59 * t * t + 0.001_813 * t * t * t);
+
+        let nutation = nutation_in_longitude(t) + dpsi;
+        let equation = nutation.to_radians() * libm::cos(obliquity.to_radians());
+        Some(wrap_angle(self.gmst() + equation))
+    }
+
+    /// The equation of time, apparent minus mean solar time.
+    ///
+    /// This uses a short series in the day of the year, accurate to
+    /// within about a minute. The result swings between about -14
+    /// minutes in February and +16 minutes in November.
+    pub fn equation_of_time(&self) -> TimeDelta<UT1> {
+        use chrono::{Datelike, Timelike};
+
+        let name = self.to_name();
+        let day =
+            name.ordinal() as f64 + name.num_seconds_from_midnight() as f64 / SECS_PER_DAY as f64;
+        let b = TAU * (day - 81.0) / 365.0;
+
+        let minutes = 9.87 * libm::sin(2.0 * b) - 7.53 * libm::cos(b) - 1.5 * libm::sin(b);
+        TimeDelta::from_seconds(minutes * 60.0)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::provider::CelestrakProvider;
+    use crate::time::UTC;
+
+    // hours, minutes, seconds of time to radians
+    fn hms(h: f64, m: f64, s: f64) -> f64 {
+        (h * 3_600.0 + m * 60.0 + s) * RAD_PER_SEC
+    }
+
+    // 1987 April 10, 0h UT1, from Meeus, Astronomical Algorithms,
+    // examples 12.a and 22.a
+    fn meeus() -> (Epoch<UT1>, CelestrakProvider) {
+        let utc = Epoch::<UTC>::from_gregorian(1987, 4, 10, 0, 0, 0).unwrap();
+        let provider = CelestrakProvider::from_rows(&[(46_800.0, 23, 0.0), (46_900.0, 23, 0.0)]);
+        (utc.transmute(), provider)
+    }
+
+    #[test]
+    fn nutation_in_longitude_meeus() {
+        let dpsi = nutation_in_longitude(-0.127_296_372_348);
+        assert!((dpsi.0 - -3.788).abs() < 0.001, "{:?}", dpsi);
+    }
+
+    #[test]
+    fn lmst_meeus() {
+        let (epoch, _) = meeus();
+        let gmst = epoch.gmst();
+        assert_eq!(epoch.lmst(0.0), gmst);
+
+        // Washington, 77°03'56" west, is about 5h 8m 15.7s behind
+        let washington = -(77.0 + 3.0 / 60.0 + 56.0 / 3_600.0_f64).to_radians();
+        let expected = gmst - hms(5.0, 8.0, 15.7333);
+        assert!((epoch.lmst(washington) - expected).abs() < hms(0.0, 0.0, 0.001));
+
+        // always wrapped into [0, 2pi)
+        for longitude in [-TAU, -gmst - 0.1, TAU - gmst + 0.1, 3.0 * TAU] {
+            let lmst = epoch.lmst(longitude);
+            assert!((0.0..TAU).contains(&lmst), "{lmst}");
+        }
+    }
+
+    #[test]
+    fn gast_meeus() {
+        let (epoch, provider) = meeus();
+        let gmst = epoch.gmst();
+        let gast = epoch.gast(&provider).unwrap();
+
+        // within 1 ms of time
+        let tolerance = hms(0.0, 0.0, 0.001);
+        assert!((gmst - hms(13.0, 10.0, 46.3668)).abs() < tolerance);
+        assert!((gast - hms(13.0, 10.0, 46.1351)).abs() < tolerance);
+    }
+
+    #[test]
+    fn gast_includes_correction() {
+        let (epoch, provider) = meeus();
+        let mut entries = provider.entries().to_vec();
+        for e in entries.iter_mut() {
+            e.dpsi = Arcsec(1.0);
+        }
+        let corrected = CelestrakProvider::from_entries(entries);
+
+        let difference = epoch.gast(&corrected).unwrap() - epoch.gast(&provider).unwrap();
+        let expected = Arcsec(1.0).to_radians() * libm::cos(Arcsec(84_381.448).to_radians());
+        assert!((difference - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gast_without_nutation() {
+        let (epoch, _) = meeus();
+        let provider = crate::provider::EmptyProvider;
+        assert_eq!(epoch.gast(&provider), None);
+    }
+
+    #[test]
+    fn earth_rotation_angle() {
+        // IAU 2000 constant term at J2000
+        let era = J2000.transmute::<UT1>().earth_rotation_angle();
+        assert!((era - TAU * 0.779_057_273_264).abs() < 1e-12);
+
+        // SOFA test case, eraEra00(2400000.5, 54388.0)
+        let era = Epoch::<UT1>::from_mjd(54_388.0).earth_rotation_angle();
+        assert!((era - 0.402_283_724_002_815_8).abs() < 1e-12, "{era}");
+
+        // always wrapped into [0, 2pi), even before J2000
+        let era = Epoch::<UT1>::from_mjd(40_000.25).earth_rotation_angle();
+        assert!((0.0..TAU).contains(&era), "{era}");
+    }
+
+    #[test]
+    fn equation_of_time() {
+        let minutes = |month, day| {
+            let utc = Epoch::<UTC>::from_gregorian(2024, month, day, 12, 0, 0).unwrap();
+            utc.transmute::<UT1>().equation_of_time().to_seconds() / 60.0
+        };
+
+        // the yearly extremes, in mid-February and early November
+        let february = minutes(2, 11);
+        assert!((-15.0..-14.0).contains(&february), "{february}");
+        let november = minutes(11, 3);
+        assert!((16.0..17.0).contains(&november), "{november}");
+
+        // and close to zero in mid-April
+        assert!(minutes(4, 15).abs() < 1.0);
+    }
+}
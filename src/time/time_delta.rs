@@ -1,16 +1,36 @@
-use super::{Scale, NANOS_PER_SEC, SECS_PER_DAY};
+use alloc::format;
+use alloc::string::String;
+
+use super::{Epoch, Scale, ToScaleWith, NANOS_PER_SEC, SECS_PER_DAY, SECS_PER_HOUR, SECS_PER_MIN};
+use crate::provider::Provider;
+
+/// An error converting between [TimeDelta] and
+/// [core::time::Duration].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DurationError {
+    #[error("negative time deltas cannot be converted to a Duration")]
+    Negative,
+    #[error("duration is out of range")]
+    OutOfRange,
+}
 
 /// A signed difference between two [Epoch][super::Epoch]s in the same [Scale].
 pub struct TimeDelta<Scale> {
     delta: chrono::TimeDelta,
-    _marker: std::marker::PhantomData<Scale>,
+    _marker: core::marker::PhantomData<Scale>,
 }
 
 impl<S> TimeDelta<S> {
+    /// The most negative representable delta.
+    pub const MIN: Self = Self::from_chrono(chrono::TimeDelta::MIN);
+
+    /// The most positive representable delta.
+    pub const MAX: Self = Self::from_chrono(chrono::TimeDelta::MAX);
+
     pub const fn from_chrono(delta: chrono::TimeDelta) -> Self {
         Self {
             delta,
-            _marker: std::marker::PhantomData,
+            _marker: core::marker::PhantomData,
         }
     }
 
@@ -22,6 +42,37 @@ impl<S> TimeDelta<S> {
         TimeDelta::from_chrono(self.to_chrono())
     }
 
+    /// Reinterpret this delta as a delta of the same length in
+    /// another [Scale].
+    ///
+    /// This is only physically meaningful between scales that tick
+    /// at the same rate, such as [TAI][super::TAI], [TT][super::TT],
+    /// and [GPS][super::GPS]. Durations in [UTC][super::UTC] may
+    /// contain leap seconds; use
+    /// [convert_duration_with][Self::convert_duration_with] for those.
+    pub const fn reinterpret<T>(self) -> TimeDelta<T> {
+        self.transmute()
+    }
+
+    /// Convert the duration starting at `anchor` into another
+    /// [Scale], using an Earth orientation [Provider].
+    ///
+    /// Both ends of the interval are converted, so any leap seconds
+    /// inside the interval are accounted for.
+    pub fn convert_duration_with<T, P>(
+        &self,
+        anchor: &Epoch<S>,
+        provider: &P,
+    ) -> Option<TimeDelta<T>>
+    where
+        Epoch<S>: ToScaleWith<T>,
+        P: Provider,
+    {
+        let start = anchor.to_scale_with(provider)?;
+        let end = (*anchor + *self).to_scale_with(provider)?;
+        Some(end - start)
+    }
+
     pub const fn new(secs: i64, nanos: u32) -> Option<Self> {
         match chrono::TimeDelta::new(secs, nanos) {
             Some(delta) => Some(Self::from_chrono(delta)),
@@ -41,20 +92,254 @@ impl<S> TimeDelta<S> {
         (secs, nanos as u32)
     }
 
+    /// Create a delta from fractional seconds, rounded to the nearest
+    /// nanosecond.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is not finite, or is beyond about ±9.2e15
+    /// s (roughly 292 million years). Use
+    /// [try_from_seconds][Self::try_from_seconds] to check instead.
     pub fn from_seconds(seconds: f64) -> Self {
-        let secs = seconds.floor();
-        let nanos = (seconds - secs) * NANOS_PER_SEC as f64;
-        match Self::new(secs as i64, nanos.floor() as u32) {
+        match Self::try_from_seconds(seconds) {
             Some(delta) => delta,
-            None => unreachable!("calculated nanos out of range"),
+            None => panic!("seconds out of range"),
         }
     }
 
+    /// Create a delta from fractional seconds, rounded to the nearest
+    /// nanosecond.
+    ///
+    /// Returns [None] if `seconds` is not finite or is out of range.
+    pub fn try_from_seconds(seconds: f64) -> Option<Self> {
+        if !seconds.is_finite() {
+            return None;
+        }
+
+        let secs = libm::floor(seconds);
+        let nanos = libm::round((seconds - secs) * NANOS_PER_SEC as f64);
+
+        // rounding can carry into the next whole second
+        let (secs, nanos) = if nanos >= NANOS_PER_SEC as f64 {
+            (secs + 1.0, 0.0)
+        } else {
+            (secs, nanos)
+        };
+
+        // float to int casts saturate, so check the range first
+        if secs < i64::MIN as f64 || secs >= i64::MAX as f64 {
+            return None;
+        }
+
+        Self::new(secs as i64, nanos as u32)
+    }
+
     pub fn to_seconds(&self) -> f64 {
         let (secs, nanos) = self.to_raw();
         secs as f64 + (nanos as f64 / NANOS_PER_SEC as f64)
     }
 
+    /// Create a delta from a total number of nanoseconds, exactly.
+    ///
+    /// Returns [None] if the result is out of range.
+    pub fn from_total_nanos(nanos: i128) -> Option<Self> {
+        let secs = nanos.div_euclid(NANOS_PER_SEC as i128);
+        let nanos = nanos.rem_euclid(NANOS_PER_SEC as i128) as u32;
+        Self::new(i64::try_from(secs).ok()?, nanos)
+    }
+
+    /// The total number of nanoseconds in this delta, exactly.
+    ///
+    /// Unlike [to_seconds][Self::to_seconds], this never loses
+    /// precision.
+    pub const fn to_total_nanos(&self) -> i128 {
+        let (secs, nanos) = self.to_raw();
+        secs as i128 * NANOS_PER_SEC as i128 + nanos as i128
+    }
+
+    /// Create a delta from whole days plus nanoseconds, exactly.
+    ///
+    /// `nanos` may be a day or more, and carries into the day count.
+    /// Returns [None] if the result is out of range.
+    pub fn from_day_nanos(days: i64, nanos: u64) -> Option<Self> {
+        let secs = days
+            .checked_mul(SECS_PER_DAY as i64)?
+            .checked_add((nanos / NANOS_PER_SEC as u64) as i64)?;
+        Self::new(secs, (nanos % NANOS_PER_SEC as u64) as u32)
+    }
+
+    /// Split this delta into whole days and nanoseconds, exactly.
+    ///
+    /// The nanoseconds are always in `[0, 86_400 * 10^9)`, so for
+    /// negative deltas the day count rounds down.
+    pub fn to_day_nanos(&self) -> (i64, u64) {
+        let (secs, nanos) = self.to_raw();
+        let days = secs.div_euclid(SECS_PER_DAY as i64);
+        let secs = secs.rem_euclid(SECS_PER_DAY as i64) as u64;
+        (days, secs * NANOS_PER_SEC as u64 + nanos as u64)
+    }
+
+    /// Add two deltas, returning [None] on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self::from_chrono(self.delta.checked_add(&rhs.delta)?))
+    }
+
+    /// Subtract two deltas, returning [None] on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Self::from_chrono(self.delta.checked_sub(&rhs.delta)?))
+    }
+
+    /// Add two deltas, clamping to [MIN][Self::MIN] or
+    /// [MAX][Self::MAX] instead of overflowing.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(if rhs.is_negative() {
+            Self::MIN
+        } else {
+            Self::MAX
+        })
+    }
+
+    /// Subtract two deltas, clamping to [MIN][Self::MIN] or
+    /// [MAX][Self::MAX] instead of overflowing.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or(if rhs.is_negative() {
+            Self::MAX
+        } else {
+            Self::MIN
+        })
+    }
+
+    /// Whether this delta is within `tolerance` of `other`,
+    /// inclusive.
+    ///
+    /// The sign of `tolerance` is ignored.
+    pub fn approx_eq(&self, other: &Self, tolerance: Self) -> bool {
+        match self.checked_sub(*other) {
+            Some(difference) => difference.abs() <= tolerance.abs(),
+            None => false,
+        }
+    }
+
+    /// The absolute value of this delta.
+    ///
+    /// The range of a delta is symmetric, so this cannot overflow.
+    pub fn abs(self) -> Self {
+        Self::from_chrono(self.delta.abs())
+    }
+
+    /// Returns `-1`, `0`, or `1` depending on the sign of this delta.
+    pub fn signum(&self) -> i32 {
+        match self.delta.cmp(&chrono::TimeDelta::zero()) {
+            core::cmp::Ordering::Less => -1,
+            core::cmp::Ordering::Equal => 0,
+            core::cmp::Ordering::Greater => 1,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.signum() < 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.signum() > 0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.signum() == 0
+    }
+
+    /// Format as signed hours, minutes, and seconds, such as
+    /// `+01:23:45.678` or `-49:00:00`.
+    ///
+    /// Hours are not wrapped into days. Fractional seconds are
+    /// written only as far as needed, down to nanoseconds. No
+    /// [Scale] name is included; use [Display][core::fmt::Display]
+    /// for that.
+    pub fn to_hms_string(&self) -> String {
+        let (sign, days, hours, minutes, secs, nanos) = self.split_hms();
+        format!(
+            "{}{:02}:{:02}:{:02}{}",
+            sign,
+            days * 24 + hours,
+            minutes,
+            secs,
+            fraction_string(nanos)
+        )
+    }
+
+    /// Format as an ISO 8601 duration, such as `PT1H23M45.678S` or
+    /// `-P2DT1H`.
+    ///
+    /// Days are always exactly 86,400 s. Zero components are left
+    /// out, and negative deltas have a leading `-`. No [Scale] name
+    /// is included.
+    pub fn to_iso8601_duration(&self) -> String {
+        let (sign, days, hours, minutes, secs, nanos) = self.split_hms();
+        let sign = if sign == '-' { "-" } else { "" };
+
+        let mut time = String::new();
+        if hours > 0 {
+            time += &format!("{}H", hours);
+        }
+        if minutes > 0 {
+            time += &format!("{}M", minutes);
+        }
+        if secs > 0 || nanos > 0 || (days == 0 && time.is_empty()) {
+            time += &format!("{}{}S", secs, fraction_string(nanos));
+        }
+
+        match (days, time.is_empty()) {
+            (0, _) => format!("{}PT{}", sign, time),
+            (_, true) => format!("{}P{}D", sign, days),
+            (_, false) => format!("{}P{}DT{}", sign, days, time),
+        }
+    }
+
+    /// Format as signed decimal days, such as `+1.5` or `-0.25`.
+    ///
+    /// Days are always exactly 86,400 s. The fraction is rounded to
+    /// 14 decimal places, enough to tell nanoseconds apart, and
+    /// written only as far as needed. No [Scale] name is included.
+    pub fn to_days_string(&self) -> String {
+        const PLACES: u32 = 14;
+        let nanos_per_day = SECS_PER_DAY as u128 * NANOS_PER_SEC as u128;
+
+        let total = self.to_total_nanos();
+        let sign = if total < 0 { '-' } else { '+' };
+        let magnitude = total.unsigned_abs();
+
+        // a nanosecond is more than one unit in the last place, so
+        // rounding never carries into whole days
+        let scale = 10u128.pow(PLACES);
+        let days = magnitude / nanos_per_day;
+        let remainder = magnitude % nanos_per_day;
+        let fraction = (remainder * scale + nanos_per_day / 2) / nanos_per_day;
+
+        if fraction == 0 {
+            return format!("{}{}", sign, days);
+        }
+        let digits = format!("{:0width$}", fraction, width = PLACES as usize);
+        format!("{}{}.{}", sign, days, digits.trim_end_matches('0'))
+    }
+
+    // split into sign, days, hours, minutes, seconds, and nanoseconds
+    fn split_hms(&self) -> (char, u128, u128, u128, u128, u32) {
+        let total = self.to_total_nanos();
+        let sign = if total < 0 { '-' } else { '+' };
+        let magnitude = total.unsigned_abs();
+
+        let nanos = (magnitude % NANOS_PER_SEC as u128) as u32;
+        let secs = magnitude / NANOS_PER_SEC as u128;
+        (
+            sign,
+            secs / SECS_PER_DAY as u128,
+            secs % SECS_PER_DAY as u128 / SECS_PER_HOUR as u128,
+            secs % SECS_PER_HOUR as u128 / SECS_PER_MIN as u128,
+            secs % SECS_PER_MIN as u128,
+            nanos,
+        )
+    }
+
     pub fn from_days(days: f64) -> Self {
         Self::from_seconds(days * SECS_PER_DAY as f64)
     }
@@ -64,31 +349,44 @@ impl<S> TimeDelta<S> {
     }
 }
 
-impl<S> std::clone::Clone for TimeDelta<S> {
+// fractional seconds with a leading dot, as short as possible
+fn fraction_string(nanos: u32) -> String {
+    if nanos == 0 {
+        return String::new();
+    }
+
+    let digits = format!("{:09}", nanos);
+    format!(".{}", digits.trim_end_matches('0'))
+}
+
+impl<S> core::clone::Clone for TimeDelta<S> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<S> std::marker::Copy for TimeDelta<S> {}
+impl<S> core::marker::Copy for TimeDelta<S> {}
 
-impl<S> std::fmt::Debug for TimeDelta<S> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<S> core::fmt::Debug for TimeDelta<S>
+where
+    S: Scale,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let (secs, nanos) = self.to_raw();
 
         f.debug_struct("TimeDelta")
             .field("secs", &secs)
             .field("nanos", &nanos)
-            .field("scale", &std::any::type_name::<S>())
+            .field("scale", &S::NAME)
             .finish()
     }
 }
 
-impl<S> std::fmt::Display for TimeDelta<S>
+impl<S> core::fmt::Display for TimeDelta<S>
 where
     S: Scale,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.write_fmt(format_args!("{} {}", self.delta, S::NAME))
     }
 }
@@ -99,48 +397,73 @@ impl<S> Default for TimeDelta<S> {
     }
 }
 
-impl<S> std::convert::From<chrono::TimeDelta> for TimeDelta<S> {
+impl<S> core::convert::From<chrono::TimeDelta> for TimeDelta<S> {
     fn from(value: chrono::TimeDelta) -> Self {
         Self::from_chrono(value)
     }
 }
 
-impl<S> std::convert::From<TimeDelta<S>> for chrono::TimeDelta {
+impl<S> core::convert::From<TimeDelta<S>> for chrono::TimeDelta {
     fn from(value: TimeDelta<S>) -> Self {
         value.to_chrono()
     }
 }
 
-impl<S> std::cmp::PartialEq for TimeDelta<S> {
+impl<S> core::convert::TryFrom<core::time::Duration> for TimeDelta<S> {
+    type Error = DurationError;
+
+    fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
+        chrono::TimeDelta::from_std(value)
+            .map(Self::from_chrono)
+            .map_err(|_| DurationError::OutOfRange)
+    }
+}
+
+impl<S> core::convert::TryFrom<TimeDelta<S>> for core::time::Duration {
+    type Error = DurationError;
+
+    fn try_from(value: TimeDelta<S>) -> Result<Self, Self::Error> {
+        if value.is_negative() {
+            return Err(DurationError::Negative);
+        }
+
+        value
+            .to_chrono()
+            .to_std()
+            .map_err(|_| DurationError::OutOfRange)
+    }
+}
+
+impl<S> core::cmp::PartialEq for TimeDelta<S> {
     fn eq(&self, other: &Self) -> bool {
         self.delta.eq(&other.delta)
     }
 }
 
-impl<S> std::cmp::Eq for TimeDelta<S> {}
+impl<S> core::cmp::Eq for TimeDelta<S> {}
 
-impl<S> std::cmp::PartialOrd for TimeDelta<S> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl<S> core::cmp::PartialOrd for TimeDelta<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.delta.partial_cmp(&other.delta)
     }
 }
 
-impl<S> std::cmp::Ord for TimeDelta<S> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+impl<S> core::cmp::Ord for TimeDelta<S> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.delta.cmp(&other.delta)
     }
 }
 
-impl<S> std::hash::Hash for TimeDelta<S> {
+impl<S> core::hash::Hash for TimeDelta<S> {
     fn hash<H>(&self, state: &mut H)
     where
-        H: std::hash::Hasher,
+        H: core::hash::Hasher,
     {
         self.delta.hash(state)
     }
 }
 
-impl<S> std::ops::Add for TimeDelta<S> {
+impl<S> core::ops::Add for TimeDelta<S> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -148,13 +471,13 @@ impl<S> std::ops::Add for TimeDelta<S> {
     }
 }
 
-impl<S> std::ops::AddAssign for TimeDelta<S> {
+impl<S> core::ops::AddAssign for TimeDelta<S> {
     fn add_assign(&mut self, rhs: Self) {
         self.delta += rhs.delta;
     }
 }
 
-impl<S> std::ops::Sub for TimeDelta<S> {
+impl<S> core::ops::Sub for TimeDelta<S> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -162,16 +485,279 @@ impl<S> std::ops::Sub for TimeDelta<S> {
     }
 }
 
-impl<S> std::ops::SubAssign for TimeDelta<S> {
+impl<S> core::ops::SubAssign for TimeDelta<S> {
     fn sub_assign(&mut self, rhs: Self) {
         self.delta -= rhs.delta;
     }
 }
 
-impl<S> std::ops::Neg for TimeDelta<S> {
+impl<S> core::ops::Neg for TimeDelta<S> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
         Self::from_chrono(-self.delta)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::TAI;
+
+    fn raw(seconds: f64) -> (i64, u32) {
+        TimeDelta::<TAI>::from_seconds(seconds).to_raw()
+    }
+
+    #[test]
+    fn from_seconds_rounds_to_nearest() {
+        assert_eq!(raw(0.999_999_999_5), (1, 0));
+        assert_eq!(raw(0.999_999_999_4), (0, 999_999_999));
+        assert_eq!(raw(1.000_000_000_4), (1, 0));
+        assert_eq!(raw(0.5), (0, 500_000_000));
+    }
+
+    #[test]
+    fn from_seconds_negative() {
+        assert_eq!(raw(-1.000_000_000_4), (-1, 0));
+        assert_eq!(raw(-0.25), (-1, 750_000_000));
+        assert_eq!(raw(-1.5), (-2, 500_000_000));
+    }
+
+    #[test]
+    fn try_from_seconds_range() {
+        assert_eq!(TimeDelta::<TAI>::try_from_seconds(f64::NAN), None);
+        assert_eq!(TimeDelta::<TAI>::try_from_seconds(f64::INFINITY), None);
+        assert_eq!(TimeDelta::<TAI>::try_from_seconds(1e300), None);
+        assert!(TimeDelta::<TAI>::try_from_seconds(-1e15).is_some());
+    }
+
+    #[test]
+    fn try_from_seconds_matches_from_seconds() {
+        for seconds in [0.0, -0.0, 1.5, -2.25, 86_400.000_000_001, -9.0e15] {
+            assert_eq!(
+                TimeDelta::<TAI>::try_from_seconds(seconds),
+                Some(TimeDelta::from_seconds(seconds))
+            );
+        }
+        assert_eq!(TimeDelta::<TAI>::try_from_seconds(-1e19), None);
+        assert_eq!(TimeDelta::<TAI>::try_from_seconds(f64::NEG_INFINITY), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "seconds out of range")]
+    fn from_seconds_panics_on_nan() {
+        TimeDelta::<TAI>::from_seconds(f64::NAN);
+    }
+
+    #[test]
+    fn reinterpret_keeps_length() {
+        let delta = TimeDelta::<TAI>::new(12, 345).unwrap();
+        assert_eq!(delta.reinterpret::<crate::time::TT>().to_raw(), (12, 345));
+    }
+
+    #[test]
+    fn convert_duration_same_rate() {
+        use crate::time::{Epoch, TT};
+
+        let delta = TimeDelta::<TT>::new(90, 0).unwrap();
+        let anchor = Epoch::<TT>::default();
+        let tai = delta.convert_duration_with::<TAI, _>(&anchor, &crate::provider::EmptyProvider);
+        assert_eq!(tai, Some(TimeDelta::new(90, 0).unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn convert_duration_across_leap_second() {
+        use crate::time::{Epoch, UTC};
+
+        let provider = crate::provider::CelestrakProvider::from_rows(&[
+            (57_753.0, 36, 0.0),
+            (57_754.0, 37, 0.0),
+            (57_755.0, 37, 0.0),
+        ]);
+        let anchor = Epoch::<UTC>::from_gregorian(2016, 12, 31, 23, 59, 0).unwrap();
+        let delta = TimeDelta::<UTC>::new(120, 0).unwrap();
+
+        let tai = delta.convert_duration_with::<TAI, _>(&anchor, &provider);
+        assert_eq!(tai, Some(TimeDelta::new(121, 0).unwrap()));
+    }
+
+    #[test]
+    fn day_nanos_round_trip() {
+        let delta = TimeDelta::<TAI>::new(2 * 86_400 + 3_600, 5).unwrap();
+        assert_eq!(delta.to_day_nanos(), (2, 3_600_000_000_005));
+        assert_eq!(TimeDelta::from_day_nanos(2, 3_600_000_000_005), Some(delta));
+
+        // negative deltas round the day down
+        let delta = TimeDelta::<TAI>::new(-1, 0).unwrap();
+        assert_eq!(delta.to_day_nanos(), (-1, 86_399_000_000_000));
+        assert_eq!(
+            TimeDelta::from_day_nanos(-1, 86_399_000_000_000),
+            Some(delta)
+        );
+
+        // whole days of nanoseconds carry
+        assert_eq!(
+            TimeDelta::<TAI>::from_day_nanos(0, 86_401_000_000_000),
+            TimeDelta::new(86_401, 0)
+        );
+        assert_eq!(TimeDelta::<TAI>::from_day_nanos(i64::MAX, 0), None);
+    }
+
+    #[test]
+    fn sign_helpers() {
+        let neg = TimeDelta::<TAI>::new(-1, 999_999_999).unwrap();
+        assert_eq!(neg.signum(), -1);
+        assert!(neg.is_negative() && !neg.is_positive() && !neg.is_zero());
+        assert_eq!(neg.abs(), TimeDelta::new(0, 1).unwrap());
+
+        let zero = TimeDelta::<TAI>::new(0, 0).unwrap();
+        assert_eq!(zero.signum(), 0);
+        assert!(zero.is_zero() && !zero.is_negative() && !zero.is_positive());
+        assert_eq!(zero.abs(), zero);
+
+        let pos = TimeDelta::<TAI>::new(2, 5).unwrap();
+        assert_eq!(pos.signum(), 1);
+        assert!(pos.is_positive());
+        assert_eq!(pos.abs(), pos);
+        assert_eq!((-pos).abs(), pos);
+    }
+
+    #[test]
+    fn duration_conversions() {
+        use core::time::Duration;
+
+        let duration = Duration::new(90, 5);
+        let delta = TimeDelta::<TAI>::try_from(duration).unwrap();
+        assert_eq!(delta, TimeDelta::new(90, 5).unwrap());
+        assert_eq!(Duration::try_from(delta), Ok(duration));
+
+        let neg = TimeDelta::<TAI>::new(-1, 0).unwrap();
+        assert_eq!(Duration::try_from(neg), Err(DurationError::Negative));
+        assert_eq!(
+            TimeDelta::<TAI>::try_from(Duration::MAX),
+            Err(DurationError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn total_nanos() {
+        let delta = TimeDelta::<TAI>::new(-2, 250).unwrap();
+        assert_eq!(delta.to_total_nanos(), -1_999_999_750);
+        assert_eq!(TimeDelta::from_total_nanos(-1_999_999_750), Some(delta));
+
+        // exact far beyond the precision of an f64
+        let big = TimeDelta::<TAI>::new(1 << 40, 1).unwrap();
+        let nanos = big.to_total_nanos();
+        assert_eq!(nanos, (1i128 << 40) * 1_000_000_000 + 1);
+        assert_eq!(TimeDelta::from_total_nanos(nanos), Some(big));
+
+        assert_eq!(TimeDelta::<TAI>::from_total_nanos(i128::MAX), None);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let one = TimeDelta::<TAI>::new(1, 0).unwrap();
+        let big = TimeDelta::<TAI>::new(i64::MAX / 1_000, 0).unwrap();
+
+        assert_eq!(one.checked_add(one), TimeDelta::new(2, 0));
+        assert_eq!(one.checked_sub(one), TimeDelta::new(0, 0));
+        assert_eq!(big.checked_add(one), None);
+        assert_eq!((-big).checked_sub(one), None);
+
+        let epoch = crate::time::Epoch::from_frameshift(big);
+        assert_eq!(epoch.checked_sub(one), Some(epoch - one));
+        assert_eq!(epoch.checked_add(one), None);
+    }
+
+    #[test]
+    fn saturating_arithmetic() {
+        let one = TimeDelta::<TAI>::new(1, 0).unwrap();
+        assert_eq!(TimeDelta::MAX.saturating_add(one), TimeDelta::MAX);
+        assert_eq!(TimeDelta::MIN.saturating_add(-one), TimeDelta::MIN);
+        assert_eq!(TimeDelta::MIN.saturating_sub(one), TimeDelta::MIN);
+        assert_eq!(TimeDelta::MAX.saturating_sub(-one), TimeDelta::MAX);
+        assert_eq!(one.saturating_add(one), TimeDelta::new(2, 0).unwrap());
+        assert_eq!(one.saturating_sub(one), TimeDelta::new(0, 0).unwrap());
+
+        // the range is symmetric
+        assert_eq!(-TimeDelta::<TAI>::MAX, TimeDelta::MIN);
+    }
+
+    #[test]
+    fn hms_string() {
+        let delta = TimeDelta::<TAI>::new(3_600 + 23 * 60 + 45, 678_000_000).unwrap();
+        assert_eq!(delta.to_hms_string(), "+01:23:45.678");
+        assert_eq!((-delta).to_hms_string(), "-01:23:45.678");
+
+        // hours are not wrapped into days
+        let delta = TimeDelta::<TAI>::new(-49 * 3_600, 0).unwrap();
+        assert_eq!(delta.to_hms_string(), "-49:00:00");
+        assert_eq!(
+            TimeDelta::<TAI>::new(0, 1).unwrap().to_hms_string(),
+            "+00:00:00.000000001"
+        );
+    }
+
+    #[test]
+    fn iso8601_duration_string() {
+        let delta = TimeDelta::<TAI>::new(3_600 + 23 * 60 + 45, 678_000_000).unwrap();
+        assert_eq!(delta.to_iso8601_duration(), "PT1H23M45.678S");
+
+        let delta = TimeDelta::<TAI>::new(-(2 * 86_400 + 3_600), 0).unwrap();
+        assert_eq!(delta.to_iso8601_duration(), "-P2DT1H");
+        let delta = TimeDelta::<TAI>::new(86_400, 0).unwrap();
+        assert_eq!(delta.to_iso8601_duration(), "P1D");
+        let delta = TimeDelta::<TAI>::new(0, 0).unwrap();
+        assert_eq!(delta.to_iso8601_duration(), "PT0S");
+    }
+
+    #[test]
+    fn days_string() {
+        let delta = TimeDelta::<TAI>::new(129_600, 0).unwrap();
+        assert_eq!(delta.to_days_string(), "+1.5");
+        let delta = TimeDelta::<TAI>::new(-21_600, 0).unwrap();
+        assert_eq!(delta.to_days_string(), "-0.25");
+        let delta = TimeDelta::<TAI>::new(10 * 86_400 + 1, 0).unwrap();
+        assert_eq!(delta.to_days_string(), "+10.00001157407407");
+
+        assert_eq!(TimeDelta::<TAI>::new(0, 0).unwrap().to_days_string(), "+0");
+        assert_eq!(
+            TimeDelta::<TAI>::new(0, 1).unwrap().to_days_string(),
+            "+0.00000000000001"
+        );
+
+        // a nanosecond short of a day is still short of it
+        let delta = TimeDelta::<TAI>::new(86_399, 999_999_999).unwrap();
+        assert_eq!(delta.to_days_string(), "+0.99999999999999");
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = TimeDelta::<TAI>::new(10, 0).unwrap();
+        let b = TimeDelta::<TAI>::new(10, 1_000).unwrap();
+        let us = TimeDelta::new(0, 1_000).unwrap();
+
+        // inclusive, symmetric, and the sign of the tolerance is ignored
+        assert!(a.approx_eq(&b, us) && b.approx_eq(&a, us));
+        assert!(a.approx_eq(&b, -us));
+        assert!(!a.approx_eq(&b, TimeDelta::new(0, 999).unwrap()));
+
+        // differences too large to represent are never equal
+        assert!(!TimeDelta::<TAI>::MAX.approx_eq(&TimeDelta::MIN, TimeDelta::MAX));
+
+        let epoch = crate::time::Epoch::from_frameshift(a);
+        assert!(epoch.approx_eq(&crate::time::Epoch::from_frameshift(b), us));
+    }
+
+    #[test]
+    fn debug_names_scale() {
+        let delta = TimeDelta::<TAI>::new(-2, 5).unwrap();
+        assert_eq!(
+            format!("{:?}", delta),
+            "TimeDelta { secs: -2, nanos: 5, scale: \"TAI\" }"
+        );
+        let tt = format!("{:?}", delta.reinterpret::<crate::time::TT>());
+        assert!(tt.ends_with("scale: \"TT\" }"), "{tt}");
+    }
+}
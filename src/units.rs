@@ -0,0 +1,133 @@
+//! Units for Earth orientation parameters.
+
+use core::f64::consts::PI;
+
+// radians per arc-second
+const RAD_PER_ARCSEC: f64 = PI / (180.0 * 3_600.0);
+
+/// An angle in arc-seconds.
+///
+/// Earth orientation data is published in arc-seconds, but most
+/// formulas want radians. Keeping the two apart in the type system
+/// avoids mixing them up. Unknown values are NaN.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Arcsec(pub f64);
+
+impl Arcsec {
+    /// An unknown angle.
+    pub const NAN: Self = Self(f64::NAN);
+
+    /// Create an [Arcsec] from an angle in radians.
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians / RAD_PER_ARCSEC)
+    }
+
+    /// This angle in radians.
+    pub fn to_radians(self) -> f64 {
+        self.0 * RAD_PER_ARCSEC
+    }
+
+    /// Create an [Arcsec] from an angle in milli-arc-seconds.
+    pub fn from_milliarcsec(mas: f64) -> Self {
+        Self(mas / 1_000.0)
+    }
+
+    /// This angle in milli-arc-seconds.
+    pub fn to_milliarcsec(self) -> f64 {
+        self.0 * 1_000.0
+    }
+
+    /// Is this angle unknown?
+    pub fn is_nan(self) -> bool {
+        self.0.is_nan()
+    }
+}
+
+impl core::ops::Add for Arcsec {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl core::ops::AddAssign for Arcsec {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl core::ops::Sub for Arcsec {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl core::ops::SubAssign for Arcsec {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl core::ops::Neg for Arcsec {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl core::ops::Mul<f64> for Arcsec {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl core::ops::Mul<Arcsec> for f64 {
+    type Output = Arcsec;
+
+    fn mul(self, rhs: Arcsec) -> Self::Output {
+        Arcsec(self * rhs.0)
+    }
+}
+
+impl core::ops::Div<f64> for Arcsec {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        // 648,000 arc-seconds in half a turn
+        assert!((Arcsec(648_000.0).to_radians() - PI).abs() < 1e-12);
+        assert!((Arcsec::from_radians(PI).0 - 648_000.0).abs() < 1e-6);
+        assert_eq!(Arcsec::from_milliarcsec(250.0), Arcsec(0.25));
+        assert_eq!(Arcsec(0.25).to_milliarcsec(), 250.0);
+
+        assert!(Arcsec::NAN.is_nan());
+        assert!(!Arcsec::default().is_nan());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut angle = Arcsec(1.5) + Arcsec(0.5) - Arcsec(0.25);
+        assert_eq!(angle, Arcsec(1.75));
+        angle += Arcsec(0.25);
+        angle -= Arcsec(1.0);
+        assert_eq!(-angle, Arcsec(-1.0));
+        assert_eq!(angle * 3.0, 3.0 * angle);
+        assert_eq!(angle / 4.0, Arcsec(0.25));
+        assert!(Arcsec(0.1) < Arcsec(0.2));
+    }
+}
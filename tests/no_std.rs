@@ -0,0 +1,26 @@
+//! Basic [Epoch] and [TimeDelta] math, from a `no_std` crate.
+//!
+//! Run with `--no-default-features` to check that the library itself
+//! builds without `std`.
+
+#![no_std]
+
+use frameshift::time::epoch::J2000;
+use frameshift::time::{Epoch, TimeDelta, TAI, TT};
+
+#[test]
+fn epoch_math() {
+    let delta = TimeDelta::<TT>::new(86_400, 500_000_000).unwrap();
+    let later = J2000 + delta;
+    assert_eq!(later - J2000, delta);
+    assert_eq!(later - delta, J2000);
+    assert!(later > J2000);
+    assert!((later.mjd() - 51_545.500_005_787).abs() < 1e-9);
+
+    let tai: Epoch<TAI> = J2000.to_tai();
+    assert_eq!(tai.to_tt(), J2000);
+    assert_eq!(
+        J2000 - tai.transmute(),
+        TimeDelta::new(32, 184_000_000).unwrap()
+    );
+}